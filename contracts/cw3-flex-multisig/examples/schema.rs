@@ -0,0 +1,17 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use cw3::{Cw3ExecuteMsg, Cw3QueryMsg};
+use cw3_flex_multisig::msg::InstantiateMsg;
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(Cw3ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw3QueryMsg), &out_dir);
+}
@@ -0,0 +1,442 @@
+use std::cmp::Ordering;
+use std::convert::TryInto;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
+};
+use cw0::{maybe_addr, Expiration};
+use cw2::set_contract_version;
+use cw3::{
+    ProposalListResponse, ProposalResponse, Status, ThresholdResponse, Vote, VoteInfo,
+    VoteListResponse, VoteResponse, VoterDetail, VoterListResponse, VoterResponse,
+};
+use cw4::Cw4Contract;
+use cw_storage_plus::{Bound, U64Key};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{next_id, Ballot, Config, Proposal, BALLOTS, CONFIG, PROPOSALS},
+    ContractError,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw3-flex-multisig";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // The group must be a cw4 contract, its total weight bounds the required weight.
+    let group_addr = Cw4Contract(deps.api.addr_validate(&msg.group_addr).map_err(|_| {
+        ContractError::InvalidGroup {
+            addr: msg.group_addr.clone(),
+        }
+    })?);
+    let total_weight =
+        group_addr
+            .total_weight(&deps.querier)
+            .map_err(|_| ContractError::InvalidGroup {
+                addr: msg.group_addr.clone(),
+            })?;
+
+    if msg.required_weight == 0 {
+        return Err(ContractError::ZeroWeight {});
+    }
+    if msg.required_weight > total_weight {
+        return Err(ContractError::UnreachableWeight {});
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let cfg = Config {
+        required_weight: msg.required_weight,
+        max_voting_period: msg.max_voting_period,
+        group_addr,
+    };
+    CONFIG.save(deps.storage, &cfg)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<Empty>, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+            earliest,
+            latest,
+        } => execute_propose(deps, env, info, title, description, msgs, earliest, latest),
+        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg>,
+    earliest: Option<Expiration>,
+    latest: Option<Expiration>,
+) -> Result<Response<Empty>, ContractError> {
+    // Only members of the group can create a proposal, their vote counts as yes
+    // unless voting opens later.
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let vote_power = cfg
+        .group_addr
+        .is_member(&deps.querier, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // max expires also used as default
+    let max_expires = cfg.max_voting_period.after(&env.block);
+    let mut expires = latest.unwrap_or(max_expires);
+    let comp = expires.partial_cmp(&max_expires);
+    if let Some(Ordering::Greater) = comp {
+        expires = max_expires;
+    } else if comp.is_none() {
+        return Err(ContractError::WrongExpiration {});
+    }
+
+    // voting can only open before the proposal expires
+    if let Some(earliest) = earliest {
+        if earliest.partial_cmp(&expires) != Some(Ordering::Less) {
+            return Err(ContractError::WrongExpiration {});
+        }
+    }
+
+    // create a proposal
+    let mut prop = Proposal {
+        title,
+        description,
+        start_height: env.block.height,
+        earliest,
+        expires,
+        msgs,
+        status: Status::Pending,
+        required_weight: cfg.required_weight,
+        total_weight: cfg.group_addr.total_weight(&deps.querier)?,
+        yes_weight: 0,
+    };
+    prop.update_status(&env.block);
+    if prop.status == Status::Open {
+        prop.yes_weight = vote_power;
+        prop.update_status(&env.block);
+    }
+    let id = next_id(deps.storage)?;
+    PROPOSALS.save(deps.storage, id.into(), &prop)?;
+
+    // add the first yes vote from voter
+    if prop.yes_weight > 0 {
+        let ballot = Ballot {
+            weight: vote_power,
+            vote: Vote::Yes,
+        };
+        BALLOTS.save(deps.storage, (id.into(), &info.sender), &ballot)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "propose")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", format!("{:?}", prop.status)))
+}
+
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<Response<Empty>, ContractError> {
+    // Members vote with their weight at the start of the proposal.
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // ensure proposal exists and can be voted on
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id.into())?;
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+    if prop.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    // only members of the group at the start of the proposal can vote
+    let vote_power = cfg
+        .group_addr
+        .member_at_height(&deps.querier, info.sender.clone(), prop.start_height)?
+        .filter(|weight| *weight >= 1)
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // cast vote if no vote previously cast
+    BALLOTS.update(
+        deps.storage,
+        (proposal_id.into(), &info.sender),
+        |bal| match bal {
+            Some(_) => Err(ContractError::AlreadyVoted {}),
+            None => Ok(Ballot {
+                weight: vote_power,
+                vote,
+            }),
+        },
+    )?;
+
+    // if yes vote, update tally
+    if vote == Vote::Yes {
+        prop.yes_weight += vote_power;
+        prop.update_status(&env.block);
+    }
+    PROPOSALS.save(deps.storage, proposal_id.into(), &prop)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", format!("{:?}", prop.status)))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // anyone can trigger this if the vote passed
+
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id.into())?;
+    // we allow execution even after the proposal "expiration" as long as all vote come in before
+    // that point. If it was approved on time, it can be executed any time.
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::WrongExecuteStatus {});
+    }
+
+    // set it to executed
+    prop.status = Status::Executed;
+    PROPOSALS.save(deps.storage, proposal_id.into(), &prop)?;
+
+    // dispatch all proposed messages
+    Ok(Response::new()
+        .add_messages(prop.msgs)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<Empty>, ContractError> {
+    // anyone can trigger this if the vote passed
+
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id.into())?;
+    if [Status::Executed, Status::Rejected, Status::Passed].contains(&prop.status) {
+        return Err(ContractError::WrongCloseStatus {});
+    }
+    if !prop.expires.is_expired(&env.block) {
+        return Err(ContractError::NotExpired {});
+    }
+
+    // set it to failed
+    prop.status = Status::Rejected;
+    PROPOSALS.save(deps.storage, proposal_id.into(), &prop)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Threshold {} => to_binary(&query_threshold(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, env, proposal_id)?),
+        QueryMsg::Vote { proposal_id, voter } => to_binary(&query_vote(deps, proposal_id, voter)?),
+        QueryMsg::ListProposals { start_after, limit } => {
+            to_binary(&list_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => to_binary(&reverse_proposals(deps, env, start_before, limit)?),
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_binary(&list_votes(deps, proposal_id, start_after, limit)?),
+        QueryMsg::Voter { address } => to_binary(&query_voter(deps, address)?),
+        QueryMsg::ListVoters { start_after, limit } => {
+            to_binary(&list_voters(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_threshold(deps: Deps) -> StdResult<ThresholdResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let total_weight = cfg.group_addr.total_weight(&deps.querier)?;
+    Ok(ThresholdResponse::AbsoluteCount {
+        weight: cfg.required_weight,
+        total_weight,
+    })
+}
+
+fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
+    let prop = PROPOSALS.load(deps.storage, id.into())?;
+    Ok(proposal_response(id, prop, &env.block))
+}
+
+fn proposal_response(id: u64, prop: Proposal, block: &BlockInfo) -> ProposalResponse {
+    let status = prop.current_status(block);
+    ProposalResponse {
+        id,
+        title: prop.title,
+        description: prop.description,
+        msgs: prop.msgs,
+        status,
+        expires: prop.expires,
+        threshold: ThresholdResponse::AbsoluteCount {
+            weight: prop.required_weight,
+            total_weight: prop.total_weight,
+        },
+    }
+}
+
+fn list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+    let props: StdResult<Vec<_>> = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|p| map_proposal(&env.block, p))
+        .collect();
+
+    Ok(ProposalListResponse { proposals: props? })
+}
+
+fn reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_before.map(Bound::exclusive_int);
+    let props: StdResult<Vec<_>> = PROPOSALS
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|p| map_proposal(&env.block, p))
+        .collect();
+
+    Ok(ProposalListResponse { proposals: props? })
+}
+
+fn map_proposal(
+    block: &BlockInfo,
+    item: StdResult<(Vec<u8>, Proposal)>,
+) -> StdResult<ProposalResponse> {
+    let (key, prop) = item?;
+    Ok(proposal_response(parse_id(&key)?, prop, block))
+}
+
+fn parse_id(data: &[u8]) -> StdResult<u64> {
+    match data[0..8].try_into() {
+        Ok(bytes) => Ok(u64::from_be_bytes(bytes)),
+        Err(_) => Err(StdError::generic_err(
+            "Corrupted data found. 8 byte expected.",
+        )),
+    }
+}
+
+fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+    let voter_addr = deps.api.addr_validate(&voter)?;
+    let prop = BALLOTS.may_load(deps.storage, (proposal_id.into(), &voter_addr))?;
+    let vote = prop.map(|b| VoteInfo {
+        voter,
+        vote: b.vote,
+        weight: b.weight,
+    });
+    Ok(VoteResponse { vote })
+}
+
+fn list_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VoteListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    let votes: StdResult<Vec<_>> = BALLOTS
+        .prefix(U64Key::from(proposal_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, ballot) = item?;
+            Ok(VoteInfo {
+                voter: String::from_utf8(key)?,
+                vote: ballot.vote,
+                weight: ballot.weight,
+            })
+        })
+        .collect();
+
+    Ok(VoteListResponse { votes: votes? })
+}
+
+fn query_voter(deps: Deps, voter: String) -> StdResult<VoterResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let voter_addr = deps.api.addr_validate(&voter)?;
+    let weight = cfg.group_addr.is_member(&deps.querier, &voter_addr)?;
+
+    Ok(VoterResponse { weight })
+}
+
+fn list_voters(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VoterListResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let voters = cfg
+        .group_addr
+        .list_members(&deps.querier, start_after, limit)?
+        .into_iter()
+        .map(|member| VoterDetail {
+            addr: member.addr,
+            weight: member.weight,
+        })
+        .collect();
+    Ok(VoterListResponse { voters })
+}
@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Empty;
+use cw0::Duration;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// cw4 group whose members vote with their weight
+    pub group_addr: String,
+    /// Weight of yes votes a proposal needs to pass
+    pub required_weight: u64,
+    pub max_voting_period: Duration,
+}
+
+/// The multisig is driven by the standard cw3 messages
+pub type ExecuteMsg = cw3::Cw3ExecuteMsg<Empty>;
+pub use cw3::Cw3QueryMsg as QueryMsg;
@@ -0,0 +1,86 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage};
+use cw0::{Duration, Expiration};
+use cw3::{Status, Vote};
+use cw4::Cw4Contract;
+use cw_storage_plus::{Item, Map, U64Key};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Config {
+    pub required_weight: u64,
+    pub max_voting_period: Duration,
+    // Total weight and voters are queried from this contract
+    pub group_addr: Cw4Contract,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Proposal {
+    pub title: String,
+    pub description: String,
+    /// Voting weights are read from the group at the beginning of this block
+    pub start_height: u64,
+    /// Voting only opens once this is reached
+    pub earliest: Option<Expiration>,
+    pub expires: Expiration,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    pub status: Status,
+    /// how many votes needed to pass
+    pub required_weight: u64,
+    /// total weight of the group when the proposal was made
+    pub total_weight: u64,
+    /// summary of existing yes votes
+    pub yes_weight: u64,
+}
+
+impl Proposal {
+    /// current_status is non-mutable and returns what the status should be.
+    /// (designed for queries)
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        let mut status = self.status;
+
+        // pending proposals open once the earliest time is reached
+        if status == Status::Pending && self.earliest.is_none_or(|e| e.is_expired(block)) {
+            status = Status::Open;
+        }
+
+        // if open, check if voting is passed or timed out
+        if status == Status::Open && self.yes_weight >= self.required_weight {
+            status = Status::Passed;
+        }
+        if status == Status::Open && self.expires.is_expired(block) {
+            status = Status::Rejected;
+        }
+
+        status
+    }
+
+    /// update_status sets the status of the proposal to current_status.
+    /// (designed for handler logic)
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+}
+
+// we cast a ballot with our chosen vote and a given weight
+// stored under the key that voted
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Ballot {
+    pub weight: u64,
+    pub vote: Vote,
+}
+
+// unique items
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+
+// multiple-item maps
+pub const BALLOTS: Map<(U64Key, &Addr), Ballot> = Map::new("votes");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+
+pub fn next_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
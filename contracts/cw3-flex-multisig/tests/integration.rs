@@ -0,0 +1,360 @@
+use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg, Empty};
+use cw0::{Duration, Expiration};
+use cw3::{ProposalResponse, Status, Vote, VoterResponse};
+use cw4::Member;
+use cw_multi_test::{next_block, App, AppBuilder, Contract, ContractWrapper, Executor};
+
+use cw3_flex_multisig::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw3_flex_multisig::ContractError;
+
+const OWNER: &str = "owner";
+const VOTER1: &str = "voter1";
+const VOTER2: &str = "voter2";
+const VOTER3: &str = "voter3";
+const SOMEBODY: &str = "somebody";
+
+fn mock_app() -> App {
+    AppBuilder::new().build()
+}
+
+fn contract_group() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn contract_multisig() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw3_flex_multisig::contract::execute,
+        cw3_flex_multisig::contract::instantiate,
+        cw3_flex_multisig::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn member(addr: &str, weight: u64) -> Member {
+    Member {
+        addr: addr.to_string(),
+        weight,
+    }
+}
+
+/// Sets up a group of three voters with weights 1, 2 and 3 and a multisig
+/// requiring a weight of 3. The block is advanced so the weights are
+/// recorded before any proposal starts.
+fn setup(app: &mut App) -> (Addr, Addr) {
+    let group_id = app.store_code(contract_group());
+    let group = app
+        .instantiate_contract(
+            group_id,
+            Addr::unchecked(OWNER),
+            &cw4_group::msg::InstantiateMsg {
+                admin: Some(OWNER.to_string()),
+                members: vec![member(VOTER1, 1), member(VOTER2, 2), member(VOTER3, 3)],
+            },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap();
+
+    let multisig_id = app.store_code(contract_multisig());
+    let multisig = app
+        .instantiate_contract(
+            multisig_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                group_addr: group.to_string(),
+                required_weight: 3,
+                max_voting_period: Duration::Height(10),
+            },
+            &[],
+            "multisig",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    (group, multisig)
+}
+
+fn pay_somebody() -> ExecuteMsg {
+    ExecuteMsg::Propose {
+        title: "Pay somebody".to_string(),
+        description: "Send 100ucudos".to_string(),
+        msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: SOMEBODY.to_string(),
+            amount: coins(100, "ucudos"),
+        })],
+        earliest: None,
+        latest: None,
+    }
+}
+
+fn query_proposal(app: &App, multisig: &Addr, proposal_id: u64) -> ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(multisig, &QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+#[test]
+fn instantiate_checks_weights() {
+    let mut app = mock_app();
+    let (group, _) = setup(&mut app);
+    let multisig_id = app.store_code(contract_multisig());
+
+    let err = app
+        .instantiate_contract(
+            multisig_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                group_addr: group.to_string(),
+                required_weight: 7,
+                max_voting_period: Duration::Height(10),
+            },
+            &[],
+            "multisig",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::UnreachableWeight {}, err.downcast().unwrap());
+
+    let err = app
+        .instantiate_contract(
+            multisig_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                group_addr: SOMEBODY.to_string(),
+                required_weight: 1,
+                max_voting_period: Duration::Height(10),
+            },
+            &[],
+            "multisig",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidGroup {
+            addr: SOMEBODY.to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn propose_vote_execute() {
+    let mut app = mock_app();
+    let (_, multisig) = setup(&mut app);
+    app.init_bank_balance(&multisig, coins(100, "ucudos"))
+        .unwrap();
+
+    // non members cannot propose
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SOMEBODY),
+            multisig.clone(),
+            &pay_somebody(),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // the proposer votes yes with weight 1
+    app.execute_contract(
+        Addr::unchecked(VOTER1),
+        multisig.clone(),
+        &pay_somebody(),
+        &[],
+    )
+    .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Open, prop.status);
+
+    // cannot execute before it passed
+    let err = app
+        .execute_contract(
+            Addr::unchecked(VOTER1),
+            multisig.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::WrongExecuteStatus {},
+        err.downcast().unwrap()
+    );
+
+    // cannot vote twice
+    let err = app
+        .execute_contract(
+            Addr::unchecked(VOTER1),
+            multisig.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::AlreadyVoted {}, err.downcast().unwrap());
+
+    // voter2 brings it to the required weight
+    app.execute_contract(
+        Addr::unchecked(VOTER2),
+        multisig.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Passed, prop.status);
+
+    // anyone can execute a passed proposal
+    app.execute_contract(
+        Addr::unchecked(SOMEBODY),
+        multisig.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Executed, prop.status);
+    let balance = app.wrap().query_balance(SOMEBODY, "ucudos").unwrap();
+    assert_eq!(100u128, balance.amount.u128());
+
+    // and only once
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SOMEBODY),
+            multisig,
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::WrongExecuteStatus {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn votes_use_weight_at_proposal_start() {
+    let mut app = mock_app();
+    let (group, multisig) = setup(&mut app);
+
+    app.execute_contract(
+        Addr::unchecked(VOTER1),
+        multisig.clone(),
+        &pay_somebody(),
+        &[],
+    )
+    .unwrap();
+
+    // voter2 is removed and somebody joins after the proposal started
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![VOTER2.to_string()],
+            add: vec![member(SOMEBODY, 5)],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let voter: VoterResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &multisig,
+            &QueryMsg::Voter {
+                address: VOTER2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(None, voter.weight);
+
+    // the new member cannot vote on the running proposal
+    let err = app
+        .execute_contract(
+            Addr::unchecked(SOMEBODY),
+            multisig.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // the removed member still votes with their old weight
+    app.execute_contract(
+        Addr::unchecked(VOTER2),
+        multisig.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Passed, prop.status);
+}
+
+#[test]
+fn pending_and_expired_proposals() {
+    let mut app = mock_app();
+    let (_, multisig) = setup(&mut app);
+    let start = app.block_info().height;
+
+    // voting only opens at earliest, the proposer has not voted yet
+    let mut msg = pay_somebody();
+    if let ExecuteMsg::Propose { earliest, .. } = &mut msg {
+        *earliest = Some(Expiration::AtHeight(start + 2));
+    }
+    app.execute_contract(Addr::unchecked(VOTER3), multisig.clone(), &msg, &[])
+        .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Pending, prop.status);
+
+    let vote = ExecuteMsg::Vote {
+        proposal_id: 1,
+        vote: Vote::Yes,
+    };
+    let err = app
+        .execute_contract(Addr::unchecked(VOTER1), multisig.clone(), &vote, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NotOpen {}, err.downcast().unwrap());
+
+    app.update_block(|block| block.height += 2);
+    app.execute_contract(Addr::unchecked(VOTER1), multisig.clone(), &vote, &[])
+        .unwrap();
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Open, prop.status);
+
+    // cannot close before it expires
+    let close = ExecuteMsg::Close { proposal_id: 1 };
+    let err = app
+        .execute_contract(Addr::unchecked(SOMEBODY), multisig.clone(), &close, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NotExpired {}, err.downcast().unwrap());
+
+    // voting period is capped at 10 blocks
+    app.update_block(|block| block.height += 10);
+    let prop = query_proposal(&app, &multisig, 1);
+    assert_eq!(Status::Rejected, prop.status);
+    app.execute_contract(Addr::unchecked(SOMEBODY), multisig.clone(), &close, &[])
+        .unwrap();
+    let err = app
+        .execute_contract(Addr::unchecked(SOMEBODY), multisig, &close, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::WrongCloseStatus {}, err.downcast().unwrap());
+}
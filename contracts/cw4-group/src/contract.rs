@@ -0,0 +1,188 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, SubMsg,
+};
+use cw0::maybe_addr;
+use cw2::set_contract_version;
+use cw4::{
+    Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
+    TotalWeightResponse,
+};
+use cw_storage_plus::Bound;
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{ADMIN, HOOKS, MEMBERS, TOTAL},
+    ContractError,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw4-group";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// Note, you can use StdResult in some functions where you do not
+// make use of the custom errors
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let admin = maybe_addr(deps.api, msg.admin)?;
+    ADMIN.set(deps.branch(), admin)?;
+
+    let mut total = 0u64;
+    for member in msg.members.into_iter() {
+        total += member.weight;
+        let member_addr = deps.api.addr_validate(&member.addr)?;
+        MEMBERS.save(deps.storage, &member_addr, &member.weight, env.block.height)?;
+    }
+    TOTAL.save(deps.storage, &total)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let api = deps.api;
+    match msg {
+        ExecuteMsg::UpdateAdmin { admin } => Ok(ADMIN.execute_update_admin(
+            deps,
+            info,
+            admin.map(|admin| api.addr_validate(&admin)).transpose()?,
+        )?),
+        ExecuteMsg::UpdateMembers { add, remove } => {
+            execute_update_members(deps, env, info, add, remove)
+        }
+        ExecuteMsg::AddHook { addr } => {
+            Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
+        }
+        ExecuteMsg::RemoveHook { addr } => {
+            Ok(HOOKS.execute_remove_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
+        }
+    }
+}
+
+pub fn execute_update_members(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    add: Vec<Member>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    // The admin adds, reweights or removes members. Every registered hook
+    // receives the list of all changes made by this message.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut total = TOTAL.load(deps.storage)?;
+    let mut diffs: Vec<MemberDiff> = vec![];
+
+    // add all new members and update total
+    for add in add.into_iter() {
+        let add_addr = deps.api.addr_validate(&add.addr)?;
+        MEMBERS.update(
+            deps.storage,
+            &add_addr,
+            env.block.height,
+            |old| -> StdResult<_> {
+                total -= old.unwrap_or_default();
+                total += add.weight;
+                diffs.push(MemberDiff::new(add.addr, old, Some(add.weight)));
+                Ok(add.weight)
+            },
+        )?;
+    }
+
+    for remove in remove.into_iter() {
+        let remove_addr = deps.api.addr_validate(&remove)?;
+        let old = MEMBERS.may_load(deps.storage, &remove_addr)?;
+        // Only process this if they were actually in the list before
+        if let Some(weight) = old {
+            diffs.push(MemberDiff::new(remove, Some(weight), None));
+            total -= weight;
+            MEMBERS.remove(deps.storage, &remove_addr, env.block.height)?;
+        }
+    }
+
+    TOTAL.save(deps.storage, &total)?;
+
+    // call all registered hooks
+    let hook = MemberChangedHookMsg::new(diffs);
+    let messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        hook.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "update_members"),
+            attr("sender", info.sender),
+        ])
+        .add_submessages(messages))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Member {
+            addr,
+            at_height: height,
+        } => to_binary(&query_member(deps, addr, height)?),
+        QueryMsg::ListMembers { start_after, limit } => {
+            to_binary(&list_members(deps, start_after, limit)?)
+        }
+        QueryMsg::TotalWeight {} => to_binary(&query_total_weight(deps)?),
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+    }
+}
+
+pub fn query_total_weight(deps: Deps) -> StdResult<TotalWeightResponse> {
+    let weight = TOTAL.load(deps.storage)?;
+    Ok(TotalWeightResponse { weight })
+}
+
+/// Returns the weight of a member, at the beginning of block `height` if given
+pub fn query_member(deps: Deps, addr: String, height: Option<u64>) -> StdResult<MemberResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let weight = match height {
+        Some(h) => MEMBERS.may_load_at_height(deps.storage, &addr, h),
+        None => MEMBERS.may_load(deps.storage, &addr),
+    }?;
+    Ok(MemberResponse { weight })
+}
+
+pub fn list_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    let members: StdResult<Vec<_>> = MEMBERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, weight) = item?;
+            Ok(Member {
+                addr: String::from_utf8(key)?,
+                weight,
+            })
+        })
+        .collect();
+
+    Ok(MemberListResponse { members: members? })
+}
@@ -0,0 +1,18 @@
+use cosmwasm_std::StdError;
+use cw_controllers::{AdminError, HookError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
@@ -0,0 +1,20 @@
+use cosmwasm_std::Addr;
+use cw4::{MEMBERS_CHANGELOG, MEMBERS_CHECKPOINTS, MEMBERS_KEY, TOTAL_KEY};
+use cw_controllers::{Admin, Hooks};
+use cw_storage_plus::{Item, SnapshotMap, Strategy};
+
+pub const ADMIN: Admin = Admin::new("admin");
+pub const HOOKS: Hooks = Hooks::new("cw4-hooks");
+
+/// Sum of the weights of all members, stored under the cw4 raw query key
+pub const TOTAL: Item<u64> = Item::new(TOTAL_KEY);
+
+/// Weight of every member. The keys follow cw4, so `Cw4Contract::is_member` can read
+/// them with a raw query, and every block is checkpointed for proposals snapshotting
+/// the voting weights.
+pub const MEMBERS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    MEMBERS_KEY,
+    MEMBERS_CHECKPOINTS,
+    MEMBERS_CHANGELOG,
+    Strategy::EveryBlock,
+);
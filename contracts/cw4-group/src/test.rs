@@ -0,0 +1,116 @@
+#![cfg(test)]
+use crate::{
+    contract::{execute, instantiate, list_members, query_member, query_total_weight},
+    msg::{ExecuteMsg, InstantiateMsg},
+    ContractError,
+};
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env, mock_info},
+    DepsMut, SubMsg,
+};
+use cw4::{Member, MemberChangedHookMsg, MemberDiff};
+use cw_controllers::AdminError;
+
+const INIT_ADMIN: &str = "association";
+const LANDLORD1: &str = "landlord1";
+const LANDLORD2: &str = "landlord2";
+const LANDLORD3: &str = "landlord3";
+
+fn do_instantiate(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        admin: Some(INIT_ADMIN.into()),
+        members: vec![
+            Member {
+                addr: LANDLORD1.into(),
+                weight: 11,
+            },
+            Member {
+                addr: LANDLORD2.into(),
+                weight: 6,
+            },
+        ],
+    };
+    let info = mock_info("creator", &[]);
+    instantiate(deps, mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn instantiate_group() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut());
+
+    let total = query_total_weight(deps.as_ref()).unwrap();
+    assert_eq!(total.weight, 17);
+    let members = list_members(deps.as_ref(), None, None).unwrap();
+    assert_eq!(members.members.len(), 2);
+
+    // pagination starts after the given address
+    let members = list_members(deps.as_ref(), Some(LANDLORD1.into()), None).unwrap();
+    assert_eq!(
+        members.members,
+        vec![Member {
+            addr: LANDLORD2.into(),
+            weight: 6
+        }]
+    );
+}
+
+#[test]
+fn update_members() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut());
+
+    // only the admin can change members
+    let msg = ExecuteMsg::UpdateMembers {
+        add: vec![],
+        remove: vec![LANDLORD1.into()],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(LANDLORD1, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+    // register a hook to see the diffs
+    let msg = ExecuteMsg::AddHook {
+        addr: String::from("lease"),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+
+    // reweight one, add one, remove one
+    let mut env = mock_env();
+    env.block.height += 5;
+    let msg = ExecuteMsg::UpdateMembers {
+        add: vec![
+            Member {
+                addr: LANDLORD2.into(),
+                weight: 0,
+            },
+            Member {
+                addr: LANDLORD3.into(),
+                weight: 3,
+            },
+        ],
+        remove: vec![LANDLORD1.into()],
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+    let hook = MemberChangedHookMsg::new(vec![
+        MemberDiff::new(LANDLORD2, Some(6), Some(0)),
+        MemberDiff::new(LANDLORD3, None, Some(3)),
+        MemberDiff::new(LANDLORD1, Some(11), None),
+    ]);
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(hook.into_cosmos_msg("lease").unwrap())]
+    );
+
+    let total = query_total_weight(deps.as_ref()).unwrap();
+    assert_eq!(total.weight, 3);
+    let member = query_member(deps.as_ref(), LANDLORD1.into(), None).unwrap();
+    assert_eq!(member.weight, None);
+    let member = query_member(deps.as_ref(), LANDLORD2.into(), None).unwrap();
+    assert_eq!(member.weight, Some(0));
+
+    // the old weights are kept until the end of the block of the change
+    let member = query_member(deps.as_ref(), LANDLORD1.into(), Some(env.block.height)).unwrap();
+    assert_eq!(member.weight, Some(11));
+    let member = query_member(deps.as_ref(), LANDLORD1.into(), Some(env.block.height + 1)).unwrap();
+    assert_eq!(member.weight, None);
+}
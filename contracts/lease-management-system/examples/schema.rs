@@ -1,8 +1,17 @@
 use std::env::current_dir;
 use std::fs::create_dir_all;
+use std::path::Path;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
-use lease_management_system::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw_controllers::{AdminResponse, ClaimsResponse, HooksResponse};
+use lease::{
+    AvailabilityResponse, AvailablePropertiesResponse, BalanceResponse, BookingsResponse,
+    ClaimablePayoutResponse, ConfigResponse, DepositStakeResponse, ExecuteMsg, InstantiateMsg,
+    InsurancePremiumMsg, LandlordOverviewResponse, LeaseChangedHookMsg, LeaseStatusResponse,
+    OffersResponse, PauseStatusResponse, PermissionsResponse, PropertyAtHeightResponse,
+    PropertyResponse, QueryMsg, RentQuoteResponse, ReputationResponse, SearchPropertiesResponse,
+    SudoMsg, TenantOverviewResponse, TotalPropertiesResponse,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -10,7 +19,39 @@ fn main() {
     create_dir_all(&out_dir).unwrap();
     remove_schemas(&out_dir).unwrap();
 
-    export_schema(&schema_for!(InstantiateMsg), &out_dir);
-    export_schema(&schema_for!(ExecuteMsg), &out_dir);
-    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schemas(&out_dir);
+}
+
+/// Writes the schema of every message and query response, tests/schema.rs checks the
+/// schema directory against it
+pub fn export_schemas(out_dir: &Path) {
+    export_schema(&schema_for!(InstantiateMsg), out_dir);
+    export_schema(&schema_for!(ExecuteMsg), out_dir);
+    export_schema(&schema_for!(QueryMsg), out_dir);
+    export_schema(&schema_for!(SudoMsg), out_dir);
+    export_schema(&schema_for!(LeaseChangedHookMsg), out_dir);
+    export_schema(&schema_for!(InsurancePremiumMsg), out_dir);
+
+    export_schema(&schema_for!(PropertyResponse), out_dir);
+    export_schema(&schema_for!(PropertyAtHeightResponse), out_dir);
+    export_schema(&schema_for!(LeaseStatusResponse), out_dir);
+    export_schema(&schema_for!(AvailablePropertiesResponse), out_dir);
+    export_schema(&schema_for!(TotalPropertiesResponse), out_dir);
+    export_schema(&schema_for!(AdminResponse), out_dir);
+    export_schema(&schema_for!(HooksResponse), out_dir);
+    export_schema(&schema_for!(ReputationResponse), out_dir);
+    export_schema(&schema_for!(ConfigResponse), out_dir);
+    export_schema(&schema_for!(RentQuoteResponse), out_dir);
+    export_schema(&schema_for!(PermissionsResponse), out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), out_dir);
+    export_schema(&schema_for!(ClaimablePayoutResponse), out_dir);
+    export_schema(&schema_for!(BalanceResponse), out_dir);
+    export_schema(&schema_for!(TenantOverviewResponse), out_dir);
+    export_schema(&schema_for!(LandlordOverviewResponse), out_dir);
+    export_schema(&schema_for!(SearchPropertiesResponse), out_dir);
+    export_schema(&schema_for!(OffersResponse), out_dir);
+    export_schema(&schema_for!(ClaimsResponse), out_dir);
+    export_schema(&schema_for!(DepositStakeResponse), out_dir);
+    export_schema(&schema_for!(BookingsResponse), out_dir);
+    export_schema(&schema_for!(AvailabilityResponse), out_dir);
 }
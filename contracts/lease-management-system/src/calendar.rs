@@ -0,0 +1,108 @@
+use cosmwasm_std::{BlockInfo, Order, StdError, StdResult, Storage, Timestamp, Uint128};
+use cw0::Expiration;
+use cw_storage_plus::{Bound, U64Key};
+
+use crate::msg::FreeWindow;
+use crate::state::{Booking, BOOKINGS};
+
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Time of a calendar bound, None for bounds given in blocks
+pub fn time_of(at: &Expiration) -> Option<Timestamp> {
+    match at {
+        Expiration::AtTime(time) => Some(*time),
+        _ => None,
+    }
+}
+
+/// Key of the booking starting at `start` on the calendar of the property
+pub fn booking_key(id: u64, start: Timestamp) -> (U64Key, U64Key) {
+    (U64Key::from(id), U64Key::from(start.nanos()))
+}
+
+/// Price of a stay at the daily rate, every started day counts in full
+pub fn stay_price(daily_rate: Uint128, start: Timestamp, end: Timestamp) -> StdResult<Uint128> {
+    let days = (end.nanos() - start.nanos()).div_ceil(NANOS_PER_DAY);
+    Ok(daily_rate.checked_mul(Uint128::from(days))?)
+}
+
+// the last booking starting before `before`, bookings do not overlap so it is the only one
+// that can still run at that time
+fn last_before(storage: &dyn Storage, id: u64, before: Timestamp) -> StdResult<Option<Booking>> {
+    let end = Bound::exclusive(U64Key::from(before.nanos()));
+    let last = BOOKINGS
+        .prefix(U64Key::from(id))
+        .range(storage, None, Some(end), Order::Descending)
+        .next()
+        .transpose()?;
+    Ok(last.map(|(_, booking)| booking))
+}
+
+/// Whether a booking overlaps the stay from `start` until `end`
+pub fn is_booked(
+    storage: &dyn Storage,
+    id: u64,
+    start: Timestamp,
+    end: Timestamp,
+) -> StdResult<bool> {
+    let last = last_before(storage, id, end)?;
+    Ok(last.is_some_and(|booking| booking.end > Expiration::AtTime(start)))
+}
+
+/// Whether a booking on the property has not ended yet
+pub fn has_upcoming(storage: &dyn Storage, id: u64, block: &BlockInfo) -> StdResult<bool> {
+    let last = BOOKINGS
+        .prefix(U64Key::from(id))
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    Ok(last.is_some_and(|(_, booking)| !booking.end.is_expired(block)))
+}
+
+/// Up to `limit` intervals between `from` and `until` without bookings, ordered by start
+pub fn free_windows(
+    storage: &dyn Storage,
+    id: u64,
+    from: Timestamp,
+    until: Timestamp,
+    limit: usize,
+) -> StdResult<Vec<FreeWindow>> {
+    let mut cursor = from;
+    if let Some(running) = last_before(storage, id, from)? {
+        cursor = cursor.max(time_bound(&running.end)?);
+    }
+
+    let mut windows = vec![];
+    let bookings = BOOKINGS.prefix(U64Key::from(id)).range(
+        storage,
+        Some(Bound::inclusive(U64Key::from(from.nanos()))),
+        Some(Bound::exclusive(U64Key::from(until.nanos()))),
+        Order::Ascending,
+    );
+    for item in bookings {
+        if windows.len() == limit {
+            return Ok(windows);
+        }
+        let (_, booking) = item?;
+        let start = time_bound(&booking.start)?;
+        if start > cursor {
+            windows.push(FreeWindow {
+                start: Expiration::AtTime(cursor),
+                end: booking.start,
+            });
+        }
+        cursor = cursor.max(time_bound(&booking.end)?);
+    }
+    if windows.len() < limit && cursor < until {
+        windows.push(FreeWindow {
+            start: Expiration::AtTime(cursor),
+            end: Expiration::AtTime(until),
+        });
+    }
+    Ok(windows)
+}
+
+/// Like time_of, failing for bounds given in blocks
+pub fn time_bound(at: &Expiration) -> StdResult<Timestamp> {
+    time_of(at).ok_or_else(|| StdError::generic_err("Calendar bounds must be given in time"))
+}
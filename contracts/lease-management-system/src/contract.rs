@@ -1,34 +1,564 @@
-use std::ops::{Add, Sub};
+use std::convert::TryInto;
+use std::ops::Add;
 
 #[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractResult, Decimal, Deps, DepsMut, Env,
+    Event, Fraction, MessageInfo, Order, OverflowError, OverflowOperation, Pair, QuerierWrapper,
+    Reply, Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128, Uint256,
 };
-use cw0::{Duration, Expiration};
+use cw0::{Duration, Expiration, NativeBalance};
+use cw2::set_contract_version;
+use cw4::{Cw4Contract, MemberChangedHookMsg};
+use cw_storage_plus::{Bound, PrimaryKey, U128Key, U64Key};
+use lease::{InsurancePremiumMsg, LeaseChangedHookMsg, LeaseEvent};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{FlatInfo, DENOM, FLAT_LIST, OWNER, RENTER_TO_FLAT_ID},
+    calendar::{
+        booking_key, free_windows, has_upcoming, is_booked, stay_price, time_bound, time_of,
+    },
+    msg::{
+        ApplicantOffer, Application, AvailabilityResponse, AvailablePropertiesResponse,
+        BalanceResponse, BookingsResponse, ClaimablePayoutResponse, ConfigResponse,
+        DepositStakeResponse, ExecuteMsg, InstantiateMsg, InsuranceInfo, LandlordOverviewResponse,
+        LeaseStatusResponse, LeaseSummary, ListedProperty, NewProperty, OffersResponse,
+        PauseStatusResponse, PermissionsResponse, PropertyAtHeightResponse, PropertyOffer,
+        PropertyResponse, QueryMsg, RentOffer, RentQuoteResponse, RentUpdate, ReputationResponse,
+        SearchPropertiesResponse, SudoMsg, TenantOverviewResponse, TotalPropertiesResponse,
+    },
+    oracle::query_rate,
+    staking::{deposit_stake, held_rewards, stake_deposit, unbonding_total, unstake_deposit},
+    state::{
+        add_total, load_total, next_property_id, offers, properties, sub_total, Booking, Config,
+        DepositStaking, EscrowKind, FlatInfo, Grant, Insurance, LeaseAction, LeaseDocument,
+        LeaseTerm, Offer, PauseState, PendingPayout, Permission, RentSchedule, Reputation,
+        Withdrawal, ACCRUED_TOTAL, ADMIN, BOOKINGS, CLAIMABLE_PAYOUTS, CLAIMABLE_TOTAL, CONFIG,
+        DENOM, ESCROW_TOTAL, HOOKS, LISTINGS, OWNER, PAUSE, PAYMENTS, PAYOUT_COUNT,
+        PENDING_PAYOUTS, PROPERTY_COUNT, PROPERTY_MANAGERS, RENTER_BALANCES, RENTER_MANAGERS,
+        REPUTATION, STAKED_TOTAL, STAKES, SUSPENDED_LANDLORDS, SWEEP_CURSOR, UNBONDING,
+        UNBONDING_TOTAL, WITHDRAWAL,
+    },
     ContractError,
 };
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:lease-management-system";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// number of blocks covered by one rent payment, until the admin changes it
+const DEFAULT_LEASE_PERIOD: u64 = 411428;
+// number of seconds covered by one rent payment of a lease ending at a time, thirty days
+const DEFAULT_LEASE_PERIOD_SECONDS: u64 = 30 * 24 * 60 * 60;
+// most units handled by one bulk message, until the admin changes it
+const DEFAULT_MAX_BATCH_SIZE: u32 = 50;
+// rent is still accepted, as late, for a tenth of a lease period after the paid period
+const GRACE_DIVISOR: u64 = 10;
+// longest fixed term in lease periods, a hundred years of monthly rent
+const MAX_TERM_PERIODS: u64 = 1200;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 // instantiate the contract
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     // Set default denom
     // Set info.sender as the owner of the contract
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let denom = String::from("acudos");
     DENOM.save(deps.storage, &denom)?;
     OWNER.save(deps.storage, &info.sender)?;
+    ADMIN.set(deps.branch(), Some(info.sender))?;
+    PROPERTY_COUNT.save(deps.storage, &0)?;
+    let landlord_group = msg
+        .landlord_group
+        .map(|addr| validate_group(deps.as_ref(), addr))
+        .transpose()?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            lease_period: DEFAULT_LEASE_PERIOD,
+            lease_period_seconds: DEFAULT_LEASE_PERIOD_SECONDS,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            pull_rent: false,
+            landlord_group,
+            insurance: None,
+            oracle: None,
+            staking: None,
+        },
+    )?;
     Ok(Response::default())
 }
 
+// checks that the address is a cw4 group contract by querying its total weight
+fn validate_group(deps: Deps, addr: String) -> Result<Cw4Contract, ContractError> {
+    let group = deps
+        .api
+        .addr_validate(&addr)
+        .map(Cw4Contract)
+        .map_err(|_| ContractError::InvalidGroup { addr: addr.clone() })?;
+    group
+        .total_weight(&deps.querier)
+        .map_err(|_| ContractError::InvalidGroup { addr })?;
+    Ok(group)
+}
+
+// loads a single property, failing with NotFound for unknown ids
+fn load_property(storage: &dyn Storage, id: u64) -> Result<FlatInfo, ContractError> {
+    properties()
+        .may_load(storage, U64Key::from(id))?
+        .ok_or(ContractError::NotFound {})
+}
+
+// saves the property and keeps the escrow total in step with its escrow
+fn save_property(
+    storage: &mut dyn Storage,
+    id: u64,
+    property: &FlatInfo,
+    height: u64,
+) -> StdResult<()> {
+    let key = U64Key::from(id);
+    let escrow = properties()
+        .may_load(storage, key.clone())?
+        .map_or(Uint128::zero(), |old| old.escrow);
+    properties().save(storage, key, property, height)?;
+    add_total(
+        storage,
+        &ESCROW_TOTAL,
+        property.escrow.saturating_sub(escrow),
+    )?;
+    sub_total(
+        storage,
+        &ESCROW_TOTAL,
+        escrow.saturating_sub(property.escrow),
+    )
+}
+
+// passes for the renter of the property and for managers it granted the permission to
+fn assert_can_manage(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    id: u64,
+    property: &FlatInfo,
+    sender: &Addr,
+    permission: Permission,
+) -> Result<(), ContractError> {
+    if property.renter == sender.as_str() {
+        return Ok(());
+    }
+    let renter = Addr::unchecked(&property.renter);
+    let grants = [
+        PROPERTY_MANAGERS.may_load(storage, (&renter, sender, U64Key::from(id)))?,
+        RENTER_MANAGERS.may_load(storage, (&renter, sender))?,
+    ];
+    if grants.iter().flatten().any(|g| g.allows(permission, block)) {
+        Ok(())
+    } else {
+        Err(ContractError::InvalidRenter {})
+    }
+}
+
+// true while the renter is not a member of the landlord group
+fn is_suspended(storage: &dyn Storage, renter: &str) -> bool {
+    SUSPENDED_LANDLORDS.has(storage, &Addr::unchecked(renter))
+}
+
+// fails for listings of renters that left the landlord group
+fn assert_not_suspended(storage: &dyn Storage, property: &FlatInfo) -> Result<(), ContractError> {
+    match is_suspended(storage, &property.renter) {
+        true => Err(ContractError::PropertySuspended {}),
+        false => Ok(()),
+    }
+}
+
+// the error every query returns for unknown property ids
+fn property_not_found() -> StdError {
+    StdError::NotFound {
+        kind: String::from("property not found"),
+    }
+}
+
+/// Works out the rent due for the current period of an accepted lease,
+/// following its rent schedule if one was agreed on.
+pub fn rent_due(
+    property: &FlatInfo,
+    block: &BlockInfo,
+    lease_period: Duration,
+) -> StdResult<Uint128> {
+    let rent = property.agreed_rent.unwrap_or(property.rent);
+    match &property.rent_schedule {
+        None => Ok(rent),
+        Some(RentSchedule::Steps(steps)) => Ok(steps
+            .iter()
+            .rev()
+            .find(|(from, _)| from.is_expired(block))
+            .map(|(_, rent)| *rent)
+            .unwrap_or(rent)),
+        Some(RentSchedule::Percentage {
+            increase,
+            every_periods,
+        }) => {
+            let accepted_at = match lease_period {
+                Duration::Height(_) => {
+                    Expiration::AtHeight(property.accepted_at.unwrap_or(block.height))
+                }
+                Duration::Time(_) => {
+                    Expiration::AtTime(property.accepted_time.unwrap_or(block.time))
+                }
+            };
+            let periods = periods_since(accepted_at, block, lease_period);
+            compound(rent, *increase, periods / every_periods)
+        }
+    }
+}
+
+/// Rent period of an accepted lease. Leases accepted before the period was kept with the
+/// lease use the configured one.
+pub fn lease_period(property: &FlatInfo, config: &Config) -> Duration {
+    property
+        .lease_period
+        .unwrap_or(Duration::Height(config.lease_period))
+}
+
+// whole lease periods from `start` until the block, both have to be in the unit of the period
+fn periods_since(start: Expiration, block: &BlockInfo, period: Duration) -> u64 {
+    match (start, period) {
+        (Expiration::AtHeight(height), Duration::Height(blocks)) => {
+            block.height.saturating_sub(height) / blocks
+        }
+        (Expiration::AtTime(time), Duration::Time(seconds)) => {
+            block.time.seconds().saturating_sub(time.seconds()) / seconds
+        }
+        _ => 0,
+    }
+}
+
+// `rent` grown by `increase` the given number of times. The factor is squared instead of
+// applied once per increase, so the cost does not grow with the age of the lease.
+fn compound(rent: Uint128, increase: Decimal, times: u64) -> StdResult<Uint128> {
+    // factors are kept in Decimal atomics
+    let one = Uint128::new(increase.denominator());
+    let mut factor = one.checked_add(Uint128::new(increase.numerator()))?;
+    let mut growth = one;
+    let mut times = times;
+    while times > 0 {
+        if times % 2 == 1 {
+            growth = mul_ratio(growth, factor, one)?;
+        }
+        times /= 2;
+        if times > 0 {
+            factor = mul_ratio(factor, factor, one)?;
+        }
+    }
+    mul_ratio(rent, growth, one)
+}
+
+// `amount * numerator / denominator`, failing instead of panicking when it does not fit
+fn mul_ratio(amount: Uint128, numerator: Uint128, denominator: Uint128) -> StdResult<Uint128> {
+    let product = amount.full_mul(numerator) / Uint256::from(denominator.u128());
+    product
+        .try_into()
+        .map_err(|_| OverflowError::new(OverflowOperation::Mul, amount, numerator).into())
+}
+
+/// Converts a rent amount of the property to acudos. Rents in a reference currency are
+/// priced with the current oracle rate, which is returned as well.
+pub fn rent_in_acudos(
+    querier: &QuerierWrapper,
+    config: &Config,
+    property: &FlatInfo,
+    amount: Uint128,
+) -> Result<(Uint128, Option<Decimal>), ContractError> {
+    let currency = match &property.currency {
+        None => return Ok((amount, None)),
+        Some(currency) => currency,
+    };
+    let oracle = config.oracle.as_ref().ok_or(ContractError::NoOracle {})?;
+    let rate = query_rate(querier, oracle, currency)?;
+    if rate.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    let numerator = Uint128::new(rate.numerator());
+    let denominator = Uint128::new(rate.denominator());
+    Ok((mul_ratio(amount, numerator, denominator)?, Some(rate)))
+}
+
+// fails if the oracle rate used moved above what the rentee accepts
+fn check_slippage(rate: Option<Decimal>, max_rate: Option<Decimal>) -> Result<(), ContractError> {
+    match (rate, max_rate) {
+        (Some(rate), Some(max_rate)) if rate > max_rate => {
+            Err(ContractError::SlippageExceeded { rate, max_rate })
+        }
+        _ => Ok(()),
+    }
+}
+
+// resolves a lease term into the expiration it ends at, periods are counted from `from`
+fn term_end(
+    term: &LeaseTerm,
+    from: Expiration,
+    lease_period: Duration,
+) -> Result<Expiration, ContractError> {
+    match term {
+        LeaseTerm::Periods(periods) => {
+            periods_after(from, lease_period, *periods).ok_or(ContractError::InvalidTerm {})
+        }
+        LeaseTerm::Until(ends) => Ok(*ends),
+    }
+}
+
+// `from` moved by the given number of periods, None if the units differ or it overflows
+fn periods_after(from: Expiration, period: Duration, periods: u64) -> Option<Expiration> {
+    match (from, period) {
+        (Expiration::AtHeight(height), Duration::Height(blocks)) => blocks
+            .checked_mul(periods)
+            .and_then(|blocks| height.checked_add(blocks))
+            .map(Expiration::AtHeight),
+        (Expiration::AtTime(time), Duration::Time(seconds)) => seconds
+            .checked_mul(periods)
+            .and_then(|seconds| seconds.checked_mul(1_000_000_000))
+            .and_then(|nanos| time.nanos().checked_add(nanos))
+            .map(|nanos| Expiration::AtTime(Timestamp::from_nanos(nanos))),
+        (Expiration::Never {}, _) => Some(Expiration::Never {}),
+        _ => None,
+    }
+}
+
+/// End of the grace window after the paid period of a lease. Until then rent is accepted as
+/// late, afterwards the rentee is in default and the lease can be terminated.
+pub fn grace_end(property: &FlatInfo, lease_period: Duration) -> Option<Expiration> {
+    let grace = match lease_period {
+        Duration::Height(blocks) => Duration::Height(blocks / GRACE_DIVISOR),
+        Duration::Time(seconds) => Duration::Time(seconds / GRACE_DIVISOR),
+    };
+    property.expires.and_then(|expires| expires.add(grace).ok())
+}
+
+/// True if the rent of an accepted lease is overdue beyond the grace window
+pub fn in_default(property: &FlatInfo, block: &BlockInfo, lease_period: Duration) -> bool {
+    grace_end(property, lease_period).is_some_and(|end| end.is_expired(block))
+}
+
+/// True if the rent of a fixed term lease is paid up to the end of its term
+pub fn fully_paid(property: &FlatInfo) -> bool {
+    match (property.expires, property.ends) {
+        (Some(expires), Some(ends)) => expires
+            .partial_cmp(&ends)
+            .is_some_and(|ord| ord != std::cmp::Ordering::Less),
+        _ => false,
+    }
+}
+
+fn validate_term(term: &LeaseTerm, block: &BlockInfo) -> Result<(), ContractError> {
+    match term {
+        LeaseTerm::Periods(periods) if *periods == 0 || *periods > MAX_TERM_PERIODS => {
+            Err(ContractError::InvalidTerm {})
+        }
+        LeaseTerm::Until(ends) if ends.is_expired(block) => Err(ContractError::InvalidTerm {}),
+        _ => Ok(()),
+    }
+}
+
+// applies a change to the reputation of an address
+fn update_reputation(
+    storage: &mut dyn Storage,
+    address: &str,
+    change: impl FnOnce(&mut Reputation),
+) -> StdResult<()> {
+    REPUTATION.update(
+        storage,
+        &Addr::unchecked(address),
+        |stats| -> StdResult<_> {
+            let mut stats = stats.unwrap_or_default();
+            change(&mut stats);
+            Ok(stats)
+        },
+    )?;
+    Ok(())
+}
+
+// adds rent released to the renter to the lifetime payments of both parties
+fn record_payment(
+    storage: &mut dyn Storage,
+    rentee: &str,
+    renter: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let rentee = Addr::unchecked(rentee);
+    let mut paid = PAYMENTS.may_load(storage, &rentee)?.unwrap_or_default();
+    paid.paid += amount;
+    PAYMENTS.save(storage, &rentee, &paid)?;
+    let renter = Addr::unchecked(renter);
+    let mut received = PAYMENTS.may_load(storage, &renter)?.unwrap_or_default();
+    received.received += amount;
+    PAYMENTS.save(storage, &renter, &received)
+}
+
+// counts a completed lease for both parties
+fn complete_lease(storage: &mut dyn Storage, property: &FlatInfo) -> StdResult<()> {
+    update_reputation(storage, &property.renter, |r| r.leases_completed += 1)?;
+    if let Some(rentee) = &property.rentee {
+        update_reputation(storage, rentee, |r| r.leases_completed += 1)?;
+    }
+    Ok(())
+}
+
+// notifies every registered hook about a lease transition
+fn lease_changed_hooks(
+    storage: &dyn Storage,
+    id: u64,
+    property: &FlatInfo,
+    event: LeaseEvent,
+) -> StdResult<Vec<SubMsg>> {
+    let rentee = property.rentee.clone().unwrap_or_default();
+    let msg = LeaseChangedHookMsg::new(id, property.renter.clone(), rentee, event);
+    HOOKS.prepare_hooks(storage, |h| msg.clone().into_cosmos_msg(h).map(SubMsg::new))
+}
+
+// Sends acudos to a renter or rentee. The transfer is a submessage replying on error, so a
+// recipient that cannot receive funds does not block the lease action, the reply records the
+// amount as claimable instead.
+fn payout(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    recipient: impl Into<String>,
+    amount: Uint128,
+) -> StdResult<SubMsg> {
+    prune_payouts(storage, block.height)?;
+    let id = PAYOUT_COUNT.may_load(storage)?.unwrap_or_default();
+    PAYOUT_COUNT.save(storage, &(id + 1))?;
+    let recipient = recipient.into();
+    let pending = PendingPayout {
+        recipient: Addr::unchecked(&recipient),
+        amount,
+        height: block.height,
+    };
+    PENDING_PAYOUTS.save(storage, U64Key::from(id), &pending)?;
+    Ok(SubMsg::reply_on_error(send_acudos(recipient, amount), id))
+}
+
+// Rent released to a renter. With `pull_rent` it accrues in the balance of the renter and
+// no message is sent, otherwise it is paid out right away.
+fn pay_renter(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    config: &Config,
+    renter: &str,
+    amount: Uint128,
+) -> StdResult<Option<SubMsg>> {
+    if !config.pull_rent {
+        return payout(storage, block, renter, amount).map(Some);
+    }
+    RENTER_BALANCES.update(
+        storage,
+        &Addr::unchecked(renter),
+        |balance| -> StdResult<_> {
+            Ok(balance.unwrap_or_default()
+                + Coin {
+                    amount,
+                    denom: String::from("acudos"),
+                })
+        },
+    )?;
+    add_total(storage, &ACCRUED_TOTAL, amount)?;
+    Ok(None)
+}
+
+// Payouts sent in earlier blocks went through, the reply removes failed ones right away.
+// Every new payout drops up to two of them, so they do not pile up.
+fn prune_payouts(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    let oldest = PENDING_PAYOUTS
+        .range(storage, None, None, Order::Ascending)
+        .take(2)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, pending) in oldest {
+        if pending.height < height {
+            PENDING_PAYOUTS.remove(storage, U64Key::from(parse_id(&key)?));
+        }
+    }
+    Ok(())
+}
+
+// sends acudos held by the contract to the given address
+fn send_acudos(to_address: impl Into<String>, amount: Uint128) -> BankMsg {
+    BankMsg::Send {
+        to_address: to_address.into(),
+        amount: vec![Coin {
+            amount,
+            denom: String::from("acudos"),
+        }],
+    }
+}
+
+// fails for empty batches and batches above the configured limit
+fn check_batch_size(config: &Config, size: usize) -> Result<(), ContractError> {
+    if size == 0 {
+        return Err(ContractError::EmptyBatch {});
+    }
+    if size > config.max_batch_size as usize {
+        return Err(ContractError::BatchTooLarge {
+            size: size as u64,
+            max: config.max_batch_size,
+        });
+    }
+    Ok(())
+}
+
+// both parties have to commit to the same agreement, or neither to one. The renter may add
+// the uri the rentee left out.
+fn sign_document(
+    signed: &mut Option<LeaseDocument>,
+    document: Option<LeaseDocument>,
+) -> Result<(), ContractError> {
+    let document = document.map(validate_document).transpose()?;
+    match (signed, document) {
+        (None, None) => {}
+        (Some(signed), Some(document)) if signed.hash == document.hash => {
+            if signed.uri.is_none() {
+                signed.uri = document.uri;
+            }
+        }
+        _ => return Err(ContractError::DocumentMismatch {}),
+    }
+    Ok(())
+}
+
+// hashes are compared in lower case hex
+fn validate_document(document: LeaseDocument) -> Result<LeaseDocument, ContractError> {
+    let hash = document.hash.to_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidDocumentHash {});
+    }
+    Ok(LeaseDocument { hash, ..document })
+}
+
+fn validate_rent_schedule(schedule: &RentSchedule) -> Result<(), ContractError> {
+    let valid = match schedule {
+        RentSchedule::Steps(steps) => {
+            !steps.is_empty()
+                && steps.iter().all(|(_, rent)| !rent.is_zero())
+                && steps.windows(2).all(|w| w[0].0 < w[1].0)
+        }
+        // at most doubling the rent with every increase
+        RentSchedule::Percentage {
+            increase,
+            every_periods,
+        } => *every_periods > 0 && *increase <= Decimal::one(),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(ContractError::InvalidRentSchedule {})
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -36,21 +566,201 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(action) = pausable_action(&msg) {
+        let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+        if pause.is_paused(action) {
+            return Err(ContractError::Paused { action });
+        }
+    }
+
     match msg {
-        ExecuteMsg::AddProperty { rent } => execute_add_property(deps, env, info, rent),
-        ExecuteMsg::AcceptLease { property_id } => {
-            execute_accept_lease(deps, env, info, property_id)
+        ExecuteMsg::AddProperty {
+            rent,
+            min_reputation,
+            currency,
+        } => {
+            let property = NewProperty {
+                rent,
+                min_reputation,
+                currency,
+            };
+            execute_add_property(deps, env, info, property)
+        }
+        ExecuteMsg::AddProperties { properties } => {
+            execute_add_properties(deps, env, info, properties)
         }
-        ExecuteMsg::RequestForLease { property_id } => {
-            execute_request_lease(deps, env, info, property_id)
+        ExecuteMsg::BulkUpdateRent { updates } => {
+            execute_bulk_update_rent(deps, env, info, updates)
         }
+        ExecuteMsg::BulkAccept { property_ids } => {
+            execute_bulk_accept(deps, env, info, property_ids)
+        }
+        ExecuteMsg::AcceptLease {
+            property_id,
+            rent_schedule,
+            document,
+        } => execute_accept_lease(deps, env, info, property_id, rent_schedule, document),
+        ExecuteMsg::RequestForLease {
+            property_id,
+            term,
+            max_rate,
+            document,
+            offer,
+        } => execute_request_lease(
+            deps,
+            env,
+            info,
+            property_id,
+            term,
+            max_rate,
+            document,
+            offer,
+        ),
         ExecuteMsg::TerminateLease { property_id } => {
             execute_terminate_lease(deps, env, info, property_id)
         }
-        ExecuteMsg::PayRent { property_id } => execute_pay_rent(deps, env, info, property_id),
+        ExecuteMsg::PayRent {
+            property_id,
+            max_rate,
+        } => execute_pay_rent(deps, env, info, property_id, max_rate),
         ExecuteMsg::RejectLease { property_id } => {
             execute_reject_lease(deps, env, info, property_id)
         }
+        ExecuteMsg::RenewLease { property_id, term } => {
+            execute_renew_lease(deps, env, info, property_id, term)
+        }
+        ExecuteMsg::ClaimDeposit { property_id } => {
+            execute_claim_deposit(deps, env, info, property_id)
+        }
+        ExecuteMsg::UpdateAdmin { admin } => {
+            let admin = admin.map(|a| deps.api.addr_validate(&a)).transpose()?;
+            Ok(ADMIN.execute_update_admin(deps, info, admin)?)
+        }
+        ExecuteMsg::AddHook { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, addr)?)
+        }
+        ExecuteMsg::RemoveHook { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            Ok(HOOKS.execute_remove_hook(&ADMIN, deps, info, addr)?)
+        }
+        ExecuteMsg::RecordDisputeLost { property_id, loser } => {
+            execute_record_dispute_lost(deps, info, property_id, loser)
+        }
+        ExecuteMsg::UpdateConfig {
+            lease_period,
+            lease_period_seconds,
+            max_batch_size,
+            pull_rent,
+        } => execute_update_config(
+            deps,
+            info,
+            lease_period,
+            lease_period_seconds,
+            max_batch_size,
+            pull_rent,
+        ),
+        ExecuteMsg::UpdateLandlordGroup { landlord_group } => {
+            execute_update_landlord_group(deps, info, landlord_group)
+        }
+        ExecuteMsg::MemberChangedHook(msg) => execute_member_changed(deps, env, info, msg),
+        ExecuteMsg::UpdateInsurance { insurance } => {
+            execute_update_insurance(deps, info, insurance)
+        }
+        ExecuteMsg::UpdateOracle { oracle } => execute_update_oracle(deps, info, oracle),
+        ExecuteMsg::UpdateStaking { staking } => execute_update_staking(deps, info, staking),
+        ExecuteMsg::GrantManager {
+            manager,
+            property_id,
+            permissions,
+            expires,
+        } => execute_grant_manager(deps, env, info, manager, property_id, permissions, expires),
+        ExecuteMsg::RevokeManager {
+            manager,
+            property_id,
+        } => execute_revoke_manager(deps, info, manager, property_id),
+        ExecuteMsg::Pause { actions } => execute_pause(deps, info, actions),
+        ExecuteMsg::Unpause { actions } => execute_unpause(deps, info, actions),
+        ExecuteMsg::ClaimPayout {} => execute_claim_payout(deps, info),
+        ExecuteMsg::ClaimUnbonded {} => execute_claim_unbonded(deps, env, info),
+        ExecuteMsg::Withdraw { amount } => execute_withdraw(deps, info, amount),
+        ExecuteMsg::AcceptOffer {
+            property_id,
+            applicant,
+            document,
+        } => execute_accept_offer(deps, env, info, property_id, applicant, document),
+        ExecuteMsg::RejectOffer {
+            property_id,
+            applicant,
+        } => execute_reject_offer(deps, env, info, property_id, applicant),
+        ExecuteMsg::CounterOffer {
+            property_id,
+            applicant,
+            rent,
+            document,
+        } => execute_counter_offer(deps, env, info, property_id, applicant, rent, document),
+        ExecuteMsg::AcceptCounter {
+            property_id,
+            max_rate,
+        } => execute_accept_counter(deps, env, info, property_id, max_rate),
+        ExecuteMsg::WithdrawOffer { property_id } => {
+            execute_withdraw_offer(deps, info, property_id)
+        }
+        ExecuteMsg::SetDailyRate {
+            property_id,
+            daily_rate,
+        } => execute_set_daily_rate(deps, env, info, property_id, daily_rate),
+        ExecuteMsg::BookProperty {
+            property_id,
+            start,
+            end,
+        } => execute_book_property(deps, env, info, property_id, start, end),
+        ExecuteMsg::CancelBooking { property_id, start } => {
+            execute_cancel_booking(deps, env, info, property_id, start)
+        }
+        ExecuteMsg::CloseBooking { property_id, start } => {
+            execute_close_booking(deps, env, property_id, start)
+        }
+        ExecuteMsg::EmergencyWithdraw { limit } => {
+            execute_emergency_withdraw(deps, env, info, limit)
+        }
+    }
+}
+
+// The action a message belongs to for the pause. Admin messages, the landlord group hook
+// and revoking managers stay available, so the admin can fix things and renters can lock
+// out managers while the contract is paused.
+fn pausable_action(msg: &ExecuteMsg) -> Option<LeaseAction> {
+    match msg {
+        ExecuteMsg::AddProperty { .. } | ExecuteMsg::AddProperties { .. } => {
+            Some(LeaseAction::AddProperty)
+        }
+        ExecuteMsg::BulkUpdateRent { .. } | ExecuteMsg::SetDailyRate { .. } => {
+            Some(LeaseAction::UpdateRent)
+        }
+        ExecuteMsg::RequestForLease { .. } => Some(LeaseAction::RequestForLease),
+        ExecuteMsg::AcceptLease { .. }
+        | ExecuteMsg::BulkAccept { .. }
+        | ExecuteMsg::AcceptOffer { .. }
+        | ExecuteMsg::AcceptCounter { .. } => Some(LeaseAction::AcceptLease),
+        ExecuteMsg::RejectLease { .. } | ExecuteMsg::RejectOffer { .. } => {
+            Some(LeaseAction::RejectLease)
+        }
+        ExecuteMsg::CounterOffer { .. } | ExecuteMsg::WithdrawOffer { .. } => {
+            Some(LeaseAction::Offer)
+        }
+        ExecuteMsg::TerminateLease { .. } => Some(LeaseAction::TerminateLease),
+        ExecuteMsg::PayRent { .. } => Some(LeaseAction::PayRent),
+        ExecuteMsg::RenewLease { .. } => Some(LeaseAction::RenewLease),
+        ExecuteMsg::ClaimDeposit { .. } => Some(LeaseAction::ClaimDeposit),
+        ExecuteMsg::GrantManager { .. } => Some(LeaseAction::GrantManager),
+        ExecuteMsg::ClaimPayout {} => Some(LeaseAction::ClaimPayout),
+        ExecuteMsg::Withdraw { .. } => Some(LeaseAction::Withdraw),
+        ExecuteMsg::ClaimUnbonded {} => Some(LeaseAction::ClaimUnbonded),
+        ExecuteMsg::BookProperty { .. }
+        | ExecuteMsg::CancelBooking { .. }
+        | ExecuteMsg::CloseBooking { .. } => Some(LeaseAction::Booking),
+        _ => None,
     }
 }
 // function is called when rentee wants to pay rent
@@ -58,245 +768,572 @@ fn execute_pay_rent(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: usize,
+    id: u64,
+    max_rate: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     // It can only be done after renter accepted the rentee.
     // Can only be called by the rentee of the flat within completion of month.
-    // If rentee pay the rent after 1 month then it is expired.
+    // If rentee pay the rent after 1 month but within the grace window then it is late, the
+    // missed months are still owed. After the grace window the rentee is in default.
     // If rentee paid rent twice in the month then rentee agrement is valid for two months.
     // If amount provided by  rentee is more than one month rent then refund the excess rent to the rentee.
+    // With an insurance pool configured, the premium is due on top of every rent and forwarded to the pool.
+    // Rents in a reference currency are converted at the current oracle rate.
 
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?.unwrap();
-
-    if id > cfg_flatlist.len() - 1 {
-        return Err(ContractError::NotFound {});
-    }
-
-    let my_property = cfg_flatlist[id].clone();
+    let mut my_property = load_property(deps.storage, id)?;
 
-    if my_property.expires == None {
+    if my_property.expires.is_none() {
         return Err(ContractError::ExpirationDoesNotExist {});
     };
 
-    if my_property.expires.unwrap().is_expired(&env.block) {
+    if let Some(ends) = my_property.ends {
+        if ends.is_expired(&env.block) {
+            return Err(ContractError::LeaseEnded {});
+        }
+    }
+    if fully_paid(&my_property) {
+        return Err(ContractError::TermFullyPaid {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let lease_period = lease_period(&my_property, &config);
+    if in_default(&my_property, &env.block, lease_period) {
         return Err(ContractError::Expired {});
     }
+    let late = my_property.expires.unwrap().is_expired(&env.block);
 
-    let rentee_cudo_index = info
+    let rentee_cudo = info
         .funds
         .iter()
-        .position(|x| x.denom == String::from("acudos"))
-        .unwrap();
-
-    let rentee_cudo = info.funds[rentee_cudo_index].amount;
-
-    if rentee_cudo < my_property.rent {
+        .find(|x| x.denom == "acudos")
+        .map(|x| x.amount)
+        .ok_or(ContractError::LessThanRent {})?;
+
+    // rent due for the current period, following the agreed schedule
+    let due = rent_due(&my_property, &env.block, lease_period)?;
+    let (rent, rate) = rent_in_acudos(&deps.querier, &config, &my_property, due)?;
+    check_slippage(rate, max_rate)?;
+    let premium = config
+        .insurance
+        .as_ref()
+        .map_or(Uint128::zero(), |insurance| rent * insurance.premium);
+
+    if rentee_cudo < rent.checked_add(premium).map_err(StdError::from)? {
         return Err(ContractError::LessThanRent {});
     };
-    let new_expiry = my_property
-        .expires
-        .unwrap()
-        .add(Duration::Height(411428u64))?;
-    cfg_flatlist[id].expires = Some(new_expiry);
-
-    let double_rent = my_property.rent + my_property.rent;
-
-    if rentee_cudo != double_rent && rentee_cudo > my_property.rent {
-        let excess_rent = rentee_cudo - my_property.rent;
-        BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![Coin {
-                amount: excess_rent,
-                denom: String::from("acudos"),
-            }],
-        };
-    }
-
-    if rentee_cudo == double_rent {
-        let new_expiry = my_property
-            .expires
-            .unwrap()
-            .add(Duration::Height(411428u64 * 2))?;
-        cfg_flatlist[id].expires = Some(new_expiry);
+    let current_expiry = my_property.expires.unwrap();
+    let new_expiry = current_expiry.add(lease_period)?;
+    my_property.expires = Some(new_expiry);
+
+    let double_rent = rent + rent;
+    let mut rent_paid = rent;
+    let mut premium_paid = premium;
+
+    if rentee_cudo == double_rent + premium + premium {
+        let new_expiry = current_expiry.add(lease_period * 2)?;
+        my_property.expires = Some(new_expiry);
+        rent_paid = double_rent;
+        premium_paid = premium + premium;
     };
 
-    FLAT_LIST.save(deps.storage, &cfg_flatlist)?;
+    save_property(deps.storage, id, &my_property, env.block.height)?;
 
-    Ok(Response::default())
+    let rentee = my_property.rentee.clone().unwrap_or_default();
+    record_payment(deps.storage, &rentee, &my_property.renter, rent_paid)?;
+    update_reputation(deps.storage, &rentee, |r| {
+        if late {
+            r.late_payments += 1
+        } else {
+            r.on_time_payments += 1
+        }
+    })?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "pay_rent")
+        .add_attribute("id", id.to_string())
+        .add_attribute("rent_due", rent)
+        .add_attribute("late", late.to_string())
+        .add_submessages(pay_renter(
+            deps.storage,
+            &env.block,
+            &config,
+            &my_property.renter,
+            rent_paid,
+        )?);
+
+    if let (Some(insurance), false) = (config.insurance, premium_paid.is_zero()) {
+        let premium = vec![Coin {
+            amount: premium_paid,
+            denom: String::from("acudos"),
+        }];
+        let msg =
+            InsurancePremiumMsg::new(id, rentee, rent).into_cosmos_msg(insurance.pool, premium)?;
+        res = res.add_attribute("premium", premium_paid).add_message(msg);
+    }
+
+    if rentee_cudo > rent_paid + premium_paid {
+        let excess_rent = rentee_cudo - rent_paid - premium_paid;
+        res = res.add_message(send_acudos(info.sender, excess_rent));
+    }
+
+    Ok(res)
 }
 
 fn execute_reject_lease(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    id: usize,
+    id: u64,
 ) -> Result<Response, ContractError> {
     // It is used to reject the rentee and release the amount locked by the rentee for a given property.
     // Can be called only by Renter of the property
     // The rent of the first month+security locked inside the contract is released to the Rentee
     // Also update the expiration date with None.
     // Update rentee with None.
+    // While the renter is suspended, the rentee may withdraw its pending request itself.
 
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?.unwrap();
+    let mut property = load_property(deps.storage, id)?;
 
-    if cfg_flatlist[id].rentee == None {
+    if property.rentee.is_none() {
         return Err(ContractError::IsNotRented {});
     };
 
-    if id > cfg_flatlist.len() - 1 {
-        return Err(ContractError::NotFound {});
-    };
-
-    if cfg_flatlist[id].renter != info.sender.to_string() {
-        return Err(ContractError::InvalidRenter {});
-    };
+    let withdrawn = property.rentee.as_deref() == Some(info.sender.as_str())
+        && is_suspended(deps.storage, &property.renter);
+    if !withdrawn {
+        assert_can_manage(
+            deps.storage,
+            &env.block,
+            id,
+            &property,
+            &info.sender,
+            Permission::AcceptReject,
+        )?;
+    }
 
-    if cfg_flatlist[id].rentee != None && cfg_flatlist[id].expires != None {
+    if property.rentee.is_some() && property.expires.is_some() {
         return Err(ContractError::IsAcceptedByRenter {});
     };
 
-    let rentee_deposite = cfg_flatlist[id].rent + cfg_flatlist[id].rent;
-
-    let codocrypto = Coin {
-        amount: rentee_deposite,
-        denom: String::from("acudos"),
-    };
-
-    BankMsg::Send {
-        to_address: cfg_flatlist[id].rentee.as_ref().unwrap().to_string(),
-        amount: vec![codocrypto],
-    };
+    let rentee = property.rentee.as_ref().unwrap();
+    let refund = payout(deps.storage, &env.block, rentee, property.escrow)?;
 
-    cfg_flatlist[id].expires = None;
-    cfg_flatlist[id].rentee = None;
+    property.clear_lease();
 
-    FLAT_LIST.save(deps.storage, &cfg_flatlist)?;
+    save_property(deps.storage, id, &property, env.block.height)?;
 
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("action", "reject_lease")
+        .add_attribute("id", id.to_string())
+        .add_submessage(refund))
 }
 
 fn execute_accept_lease(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: usize,
+    id: u64,
+    rent_schedule: Option<RentSchedule>,
+    document: Option<LeaseDocument>,
 ) -> Result<Response, ContractError> {
     // Can be called only by Renter of the property
     // The rent of the first month locked inside the contract is released to the Renter
     // Also update the expiration date with one month.
+    // An optional rent schedule fixes the rent increases for the following months.
+    // If the rentee requested a fixed term, the lease ends after it.
+    // If the rentee signed a document, the renter must sign the same one for funds to be released.
+
+    let config = CONFIG.load(deps.storage)?;
+    let (property, first_rent) = accept_lease(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        id,
+        rent_schedule,
+        document,
+        &config,
+    )?;
+
+    let hooks = lease_changed_hooks(deps.storage, id, &property, LeaseEvent::Started)?;
+    let first_rent = pay_renter(
+        deps.storage,
+        &env.block,
+        &config,
+        &property.renter,
+        first_rent,
+    )?;
+    let stake = stake_deposit(
+        deps.storage,
+        &deps.querier,
+        &env,
+        config.staking.as_ref(),
+        id,
+        property.escrow,
+    )?;
 
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?.unwrap();
+    Ok(Response::new()
+        .add_attribute("action", "accept_lease")
+        .add_attribute("id", id.to_string())
+        .add_submessages(first_rent)
+        .add_submessages(stake)
+        .add_submessages(hooks))
+}
 
-    if id > cfg_flatlist.len() - 1 {
-        return Err(ContractError::NotFound {});
-    };
+fn execute_bulk_accept(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    // Accepts the pending rentees of several properties as AcceptLease does, without rent
+    // schedules. If any of them cannot be accepted, none is. The first rents are released
+    // with a single transfer per renter, a manager may accept for several renters.
+    let config = CONFIG.load(deps.storage)?;
+    check_batch_size(&config, ids.len())?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "bulk_accept")
+        .add_attribute("count", ids.len().to_string());
+    let mut first_rents: Vec<(String, Uint128)> = vec![];
+    for id in ids {
+        let (property, first_rent) = accept_lease(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            id,
+            None,
+            None,
+            &config,
+        )?;
+        let hooks = lease_changed_hooks(deps.storage, id, &property, LeaseEvent::Started)?;
+        let stake = stake_deposit(
+            deps.storage,
+            &deps.querier,
+            &env,
+            config.staking.as_ref(),
+            id,
+            property.escrow,
+        )?;
+        match first_rents.iter_mut().find(|(r, _)| r == &property.renter) {
+            Some((_, total)) => *total += first_rent,
+            None => first_rents.push((property.renter.clone(), first_rent)),
+        }
+        res = res.add_submessages(stake).add_submessages(hooks).add_event(
+            Event::new("accept_lease")
+                .add_attribute("id", id.to_string())
+                .add_attribute("rentee", property.rentee.unwrap_or_default())
+                .add_attribute("first_rent", first_rent),
+        );
+    }
 
-    if cfg_flatlist[id].renter != info.sender.to_string() {
-        return Err(ContractError::InvalidRenter {});
-    };
+    for (renter, amount) in first_rents {
+        res = res.add_submessages(pay_renter(
+            deps.storage,
+            &env.block,
+            &config,
+            &renter,
+            amount,
+        )?);
+    }
+    Ok(res)
+}
 
-    if cfg_flatlist[id].rentee == None {
+// starts the lease requested on the property and returns it with the first rent to release
+fn accept_lease(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    sender: &Addr,
+    id: u64,
+    rent_schedule: Option<RentSchedule>,
+    document: Option<LeaseDocument>,
+    config: &Config,
+) -> Result<(FlatInfo, Uint128), ContractError> {
+    let mut property = load_property(storage, id)?;
+
+    assert_can_manage(
+        storage,
+        block,
+        id,
+        &property,
+        sender,
+        Permission::AcceptReject,
+    )?;
+
+    if property.rentee.is_none() {
         return Err(ContractError::IsNotRented {});
     };
 
-    let t = env.block.height + 411428;
-    cfg_flatlist[id].expires = Some(Expiration::AtHeight(t));
-
-    let rentee_deposite = cfg_flatlist[id].rent + cfg_flatlist[id].rent;
-
-    let codocrypto = Coin {
-        amount: rentee_deposite,
-        denom: String::from("acudos"),
+    if property.expires.is_some() {
+        return Err(ContractError::IsAcceptedByRenter {});
     };
 
-    BankMsg::Send {
-        to_address: cfg_flatlist[id].rentee.as_ref().unwrap().to_string(),
-        amount: vec![codocrypto],
-    };
+    assert_not_suspended(storage, &property)?;
 
-    FLAT_LIST.save(deps.storage, &cfg_flatlist)?;
+    if let Some(schedule) = &rent_schedule {
+        validate_rent_schedule(schedule)?;
+    }
 
-    Ok(Response::default())
+    sign_document(&mut property.document, document)?;
+
+    // the period is fixed for the whole lease, changes of the config do not apply to it.
+    // A term ending at a time is paid in periods of time, so both can be compared.
+    let (lease_period, accepted) = match &property.term {
+        Some(LeaseTerm::Until(Expiration::AtTime(_))) => (
+            Duration::Time(config.lease_period_seconds),
+            Expiration::AtTime(block.time),
+        ),
+        _ => (
+            Duration::Height(config.lease_period),
+            Expiration::AtHeight(block.height),
+        ),
+    };
+    property.expires = Some(lease_period.after(block));
+    property.ends = property
+        .term
+        .as_ref()
+        .map(|term| term_end(term, accepted, lease_period))
+        .transpose()?;
+    property.rent_schedule = rent_schedule;
+    property.accepted_at = Some(block.height);
+    property.accepted_time = Some(block.time);
+    property.lease_period = Some(lease_period);
+
+    // the first rent is released, the deposit stays in escrow
+    let first_rent = property.escrow.multiply_ratio(1u128, 2u128);
+    property.escrow = property
+        .escrow
+        .checked_sub(first_rent)
+        .map_err(StdError::from)?;
+
+    save_property(storage, id, &property, block.height)?;
+    record_payment(
+        storage,
+        property.rentee.as_deref().unwrap_or_default(),
+        &property.renter,
+        first_rent,
+    )?;
+    Ok((property, first_rent))
 }
 
 fn execute_add_property(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    rent: Uint128,
+    property: NewProperty,
 ) -> Result<Response, ContractError> {
     // It is used to list the property for rent. The caller of this function will be the renter of that property.
     // If the renter is listing property first time, will register as a renter else update list with newly listed properties
     // Rent amount must be in the native currency of the chain ie.. cudos in this case.
     // Property is assigned with propertyid
     // PropertyId is auto-incremental id ie... if the contract has 100 properties (listed by different renter) then id would start from 1 to 100 and the next property id will be 101.
+    // If a landlord group is configured, only its members with a positive weight can list.
+    // The rent may be given in a reference currency if a price oracle is configured.
 
-    let data = FlatInfo {
-        renter: info.sender.clone().to_string(),
-        rentee: None,
-        rent: rent,
-        expires: None,
-    };
+    let config = CONFIG.load(deps.storage)?;
+    let ids = list_properties(deps, &env, &info.sender, config, vec![property])?;
 
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?;
+    Ok(Response::new()
+        .add_attribute("action", "add_property")
+        .add_attribute("id", ids[0].to_string()))
+}
 
-    if cfg_flatlist == None {
-        cfg_flatlist = Some(vec![data.clone()]);
-        FLAT_LIST.save(deps.storage, &cfg_flatlist.clone().unwrap())?;
-    } else {
-        FLAT_LIST.update(deps.storage, |mut val| -> StdResult<Vec<FlatInfo>> {
-            val.push(data);
-            Ok(val)
-        })?;
-    };
+fn execute_add_properties(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    properties: Vec<NewProperty>,
+) -> Result<Response, ContractError> {
+    // Lists every property as AddProperty does, all or none of them. They get consecutive ids.
+    let config = CONFIG.load(deps.storage)?;
+    check_batch_size(&config, properties.len())?;
+
+    let rents: Vec<_> = properties.iter().map(|p| p.rent).collect();
+    let ids = list_properties(deps, &env, &info.sender, config, properties)?;
+
+    let events = ids.iter().zip(rents).map(|(id, rent)| {
+        Event::new("add_property")
+            .add_attribute("id", id.to_string())
+            .add_attribute("rent", rent)
+    });
+    Ok(Response::new()
+        .add_attribute("action", "add_properties")
+        .add_attribute("count", ids.len().to_string())
+        .add_events(events))
+}
 
-    let mut l = FLAT_LIST.load(deps.storage)?.len();
-    if l.gt(&usize::from(0u16)) {
-        l = l.sub(usize::from(1u16));
+// checks that the renter may list the properties and stores them, returns their ids
+fn list_properties(
+    deps: DepsMut,
+    env: &Env,
+    renter: &Addr,
+    config: Config,
+    listings: Vec<NewProperty>,
+) -> Result<Vec<u64>, ContractError> {
+    // rented properties are in the rent index under an empty currency
+    if listings.iter().any(|p| p.currency.as_deref() == Some("")) {
+        return Err(ContractError::InvalidCurrency {});
     }
-    RENTER_TO_FLAT_ID.update(deps.storage, &info.sender, |val| -> StdResult<Vec<usize>> {
-        match val {
-            Some(mut h) => {
-                h.push(l);
-                Ok(h)
-            }
-            None => {
-                Ok(vec![l])
-            }
+    if config.oracle.is_none() && listings.iter().any(|p| p.currency.is_some()) {
+        return Err(ContractError::NoOracle {});
+    }
+
+    if let Some(group) = config.landlord_group {
+        let weight = group.is_member(&deps.querier, renter)?;
+        if weight.is_none_or(|w| w == 0) {
+            return Err(ContractError::NotALandlord {});
         }
-    })?;
+    }
 
-    Ok(Response::default())
+    let mut ids = Vec::with_capacity(listings.len());
+    for property in listings {
+        let data = FlatInfo {
+            renter: renter.to_string(),
+            rentee: None,
+            rent: property.rent,
+            currency: property.currency,
+            expires: None,
+            rent_schedule: None,
+            accepted_at: None,
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::zero(),
+            min_reputation: property.min_reputation,
+            document: None,
+            daily_rate: None,
+            accepted_time: None,
+            lease_period: None,
+            agreed_rent: None,
+        };
+
+        let id = next_property_id(deps.storage)?;
+        save_property(deps.storage, id, &data, env.block.height)?;
+        ids.push(id);
+    }
+    let listed = LISTINGS.may_load(deps.storage, renter)?.unwrap_or_default();
+    LISTINGS.save(deps.storage, renter, &(listed + ids.len() as u64))?;
+
+    Ok(ids)
+}
+
+fn execute_bulk_update_rent(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: Vec<RentUpdate>,
+) -> Result<Response, ContractError> {
+    // Re-prices listings of the sender. A rent cannot change once a rentee requested the
+    // property, the rentee locked funds for the old rent. If any update fails, none applies.
+    let config = CONFIG.load(deps.storage)?;
+    check_batch_size(&config, updates.len())?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "bulk_update_rent")
+        .add_attribute("count", updates.len().to_string());
+    for RentUpdate { property_id, rent } in updates {
+        let mut property = load_property(deps.storage, property_id)?;
+
+        assert_can_manage(
+            deps.storage,
+            &env.block,
+            property_id,
+            &property,
+            &info.sender,
+            Permission::Reprice,
+        )?;
+
+        if property.rentee.is_some() {
+            return Err(ContractError::IsRented {});
+        }
+
+        let old_rent = property.rent;
+        property.rent = rent;
+        save_property(deps.storage, property_id, &property, env.block.height)?;
+
+        res = res.add_event(
+            Event::new("update_rent")
+                .add_attribute("id", property_id.to_string())
+                .add_attribute("old_rent", old_rent)
+                .add_attribute("rent", rent),
+        );
+    }
+    Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_request_lease(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    id: usize,
+    id: u64,
+    term: Option<LeaseTerm>,
+    max_rate: Option<Decimal>,
+    document: Option<LeaseDocument>,
+    offer: Option<RentOffer>,
 ) -> Result<Response, ContractError> {
     // The caller of this function is Rentee who wants to rent a property and will pay rent + security in desired denomination mentioned in the contract ie.. native currency.
     // Locks rent of the first month with a security deposit which is equivalent to one month rent to the contract ie.. rentee needs to lock 2x amount of rent.
     // This rent of the first month + security is released when the Renter of the property accepts the rent.
     // If amount provided by rentee is more than one month rent + security then refund the excess rent to the rentee.
-
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?.unwrap();
-    let caller_is_renter = cfg_flatlist
-        .iter()
-        .any(|a| a.renter.as_ref() == info.sender.to_string());
+    // The rentee may ask for a fixed term. If the previous fixed term lease has ended, it is closed
+    // and its deposit is returned to the previous rentee.
+    // With an offer below the asking rent, twice the offered rent is locked in an offer instead
+    // and the property stays open for requests.
+
+    let caller_is_renter = properties()
+        .idx
+        .renter
+        .prefix(info.sender.as_bytes().to_vec())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
 
     if caller_is_renter {
         return Err(ContractError::InvalidRentee {});
     }
 
-    if id > cfg_flatlist.len() - 1 {
-        return Err(ContractError::NotFound {});
+    let mut property = load_property(deps.storage, id)?;
+    let mut res = Response::new();
+
+    if property
+        .ends
+        .is_some_and(|ends| ends.is_expired(&env.block))
+    {
+        res = res.add_submessages(settle_term(
+            deps.storage,
+            &deps.querier,
+            &env,
+            id,
+            &mut property,
+        )?);
     }
 
-    if cfg_flatlist[id].rentee != None {
+    if property.rentee.is_some() {
         return Err(ContractError::RenteeExist {});
     }
 
+    assert_not_suspended(deps.storage, &property)?;
+
+    if has_upcoming(deps.storage, id, &env.block)? {
+        return Err(ContractError::PropertyBooked {});
+    }
+
+    if let Some(term) = &term {
+        validate_term(term, &env.block)?;
+    }
+
+    let document = document.map(validate_document).transpose()?;
+
+    if let Some(required) = property.min_reputation {
+        let score = REPUTATION
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default()
+            .score();
+        if score < required {
+            return Err(ContractError::ReputationTooLow { score, required });
+        }
+    }
+
     let denom_check = info
         .funds
         .iter()
@@ -306,102 +1343,1970 @@ fn execute_request_lease(
         return Err(ContractError::InvalidDenom {});
     }
 
-    let rent = cfg_flatlist[id].rent;
-    let amount_to_pay = rent + rent;
+    // rents in a reference currency are locked at the current oracle rate
+    let config = CONFIG.load(deps.storage)?;
+    let offered = offer.as_ref().map_or(property.rent, |offer| offer.rent);
+    if offer.is_some() && (offered.is_zero() || offered >= property.rent) {
+        return Err(ContractError::InvalidOffer {});
+    }
+    let (rent, rate) = rent_in_acudos(&deps.querier, &config, &property, offered)?;
+    check_slippage(rate, max_rate)?;
+    let amount_to_pay = rent.checked_add(rent).map_err(StdError::from)?;
 
-    if info
+    let denom = DENOM.load(deps.storage)?;
+    let paid = info
         .funds
         .iter()
-        .any(|x| x.amount >= amount_to_pay && x.denom == DENOM.load(deps.storage).unwrap())
-        == false
-    {
-        return Err(ContractError::LessThanRent {});
-    };
+        .find(|x| x.amount >= amount_to_pay && x.denom == denom)
+        .map(|x| x.amount)
+        .ok_or(ContractError::LessThanRent {})?;
+
+    if let Some(offer) = offer {
+        let expires = offer
+            .expires
+            .unwrap_or(Expiration::AtHeight(env.block.height + config.lease_period));
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::InvalidOffer {});
+        }
+        let key = (U64Key::from(id), &info.sender);
+        if offers().may_load(deps.storage, key.clone())?.is_some() {
+            return Err(ContractError::OfferExists {});
+        }
+        let offer = Offer {
+            rent: offered,
+            counter: None,
+            counter_document: None,
+            escrow: amount_to_pay,
+            expires,
+            term,
+            document,
+        };
+        offers().save(deps.storage, key, &offer)?;
+        add_total(deps.storage, &ESCROW_TOTAL, offer.escrow)?;
+        res = res
+            .add_attribute("action", "offer")
+            .add_attribute("rent", offered);
+    } else {
+        property.rentee = Some(info.sender.to_string());
+        property.term = term;
+        property.escrow = amount_to_pay;
+        property.document = document;
+        res = res.add_attribute("action", "refund");
+    }
 
-    cfg_flatlist[id].rentee = Some(info.sender.to_string());
+    save_property(deps.storage, id, &property, env.block.height)?;
 
-    FLAT_LIST.save(deps.storage, &cfg_flatlist)?;
+    if paid > amount_to_pay {
+        res = res.add_message(send_acudos(info.sender, paid - amount_to_pay));
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "refund")
-        .add_attribute("id", id.to_string()))
+    Ok(res.add_attribute("id", id.to_string()))
 }
 
-fn execute_terminate_lease(
+fn execute_accept_offer(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: usize,
+    id: u64,
+    applicant: String,
+    document: Option<LeaseDocument>,
 ) -> Result<Response, ContractError> {
-    // Can be called by Renter of the property and is used to terminate the lease only if Rentee defaults on any month rent.
-    // Release the security deposit to rentee.
-    // Update the expiration date with None
-    // Remove the rentee with that property id.
-    let mut cfg_flatlist = FLAT_LIST.may_load(deps.storage)?.unwrap();
+    // The renter, or a manager allowed to accept, takes the offer as it is. Other offers on
+    // the property stay open until their applicants withdraw them.
+    let applicant = deps.api.addr_validate(&applicant)?;
+    let property = load_property(deps.storage, id)?;
+    assert_can_manage(
+        deps.storage,
+        &env.block,
+        id,
+        &property,
+        &info.sender,
+        Permission::AcceptReject,
+    )?;
+    let offer = load_open_offer(deps.storage, &env.block, id, &applicant)?;
+
+    let rent = offer.rent;
+    let escrow = offer.escrow;
+    start_offered_lease(
+        deps,
+        env,
+        info.sender,
+        id,
+        applicant,
+        offer,
+        document,
+        rent,
+        escrow,
+    )
+    .map(|res| res.add_attribute("action", "accept_offer"))
+}
+
+fn execute_accept_counter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    max_rate: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    // The applicant takes the counter offer of the renter, locking twice the countered rent at
+    // the current oracle rate. Funds beyond that are refunded.
+    let offer = load_open_offer(deps.storage, &env.block, id, &info.sender)?;
+    let counter = offer.counter.ok_or(ContractError::NoCounter {})?;
+    let property = load_property(deps.storage, id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let (rent, rate) = rent_in_acudos(&deps.querier, &config, &property, counter)?;
+    check_slippage(rate, max_rate)?;
+    let escrow = rent.checked_add(rent).map_err(StdError::from)?;
+
+    let paid = info
+        .funds
+        .iter()
+        .find(|c| c.denom == "acudos")
+        .map_or(Uint128::zero(), |c| c.amount);
+    let funds = offer.escrow + paid;
+    if funds < escrow {
+        return Err(ContractError::LessThanRent {});
+    }
+
+    let renter = Addr::unchecked(&property.renter);
+    let document = offer.counter_document.clone();
+    let res = start_offered_lease(
+        deps,
+        env,
+        renter,
+        id,
+        info.sender.clone(),
+        offer,
+        document,
+        counter,
+        escrow,
+    )?;
+    let res = res.add_attribute("action", "accept_counter");
+    if funds > escrow {
+        return Ok(res.add_message(send_acudos(info.sender, funds - escrow)));
+    }
+    Ok(res)
+}
+
+// Turns the offer into an accepted lease at the agreed rent, which becomes the rent of the
+// property. `sender` accepts as the renter or its manager, `document` is the agreement the
+// renter signed and must match the one of the offer.
+#[allow(clippy::too_many_arguments)]
+fn start_offered_lease(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+    applicant: Addr,
+    offer: Offer,
+    document: Option<LeaseDocument>,
+    rent: Uint128,
+    escrow: Uint128,
+) -> Result<Response, ContractError> {
+    let mut property = load_property(deps.storage, id)?;
+    if property.rentee.is_some() {
+        return Err(ContractError::RenteeExist {});
+    }
+    assert_not_suspended(deps.storage, &property)?;
+    if has_upcoming(deps.storage, id, &env.block)? {
+        return Err(ContractError::PropertyBooked {});
+    }
+    let mut signed = offer.document;
+    sign_document(&mut signed, document)?;
+    offers().remove(deps.storage, (U64Key::from(id), &applicant))?;
+    sub_total(deps.storage, &ESCROW_TOTAL, offer.escrow)?;
+
+    property.agreed_rent = Some(rent);
+    property.rentee = Some(applicant.to_string());
+    property.term = offer.term;
+    property.escrow = escrow;
+    property.document = signed.clone();
+    save_property(deps.storage, id, &property, env.block.height)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let (property, first_rent) = accept_lease(
+        deps.storage,
+        &env.block,
+        &sender,
+        id,
+        None,
+        // matched with the document of the offer above
+        signed,
+        &config,
+    )?;
+    let hooks = lease_changed_hooks(deps.storage, id, &property, LeaseEvent::Started)?;
+    let first_rent = pay_renter(
+        deps.storage,
+        &env.block,
+        &config,
+        &property.renter,
+        first_rent,
+    )?;
+    let stake = stake_deposit(
+        deps.storage,
+        &deps.querier,
+        &env,
+        config.staking.as_ref(),
+        id,
+        property.escrow,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("id", id.to_string())
+        .add_attribute("rentee", applicant)
+        .add_attribute("rent", rent)
+        .add_submessages(first_rent)
+        .add_submessages(stake)
+        .add_submessages(hooks))
+}
+
+fn execute_reject_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    applicant: String,
+) -> Result<Response, ContractError> {
+    let applicant = deps.api.addr_validate(&applicant)?;
+    let property = load_property(deps.storage, id)?;
+    assert_can_manage(
+        deps.storage,
+        &env.block,
+        id,
+        &property,
+        &info.sender,
+        Permission::AcceptReject,
+    )?;
+    let key = (U64Key::from(id), &applicant);
+    let offer = offers()
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoOffer {})?;
+    offers().remove(deps.storage, key)?;
+    sub_total(deps.storage, &ESCROW_TOTAL, offer.escrow)?;
+
+    let refund = payout(deps.storage, &env.block, &applicant, offer.escrow)?;
+    Ok(Response::new()
+        .add_attribute("action", "reject_offer")
+        .add_attribute("id", id.to_string())
+        .add_attribute("applicant", applicant)
+        .add_submessage(refund))
+}
+
+fn execute_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    applicant: String,
+    rent: Uint128,
+    document: Option<LeaseDocument>,
+) -> Result<Response, ContractError> {
+    let applicant = deps.api.addr_validate(&applicant)?;
+    let property = load_property(deps.storage, id)?;
+    assert_can_manage(
+        deps.storage,
+        &env.block,
+        id,
+        &property,
+        &info.sender,
+        Permission::AcceptReject,
+    )?;
+    let mut offer = load_open_offer(deps.storage, &env.block, id, &applicant)?;
+    if rent <= offer.rent || rent > property.rent {
+        return Err(ContractError::InvalidCounter {});
+    }
+    offer.counter = Some(rent);
+    offer.counter_document = document.map(validate_document).transpose()?;
+    offers().save(deps.storage, (U64Key::from(id), &applicant), &offer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "counter_offer")
+        .add_attribute("id", id.to_string())
+        .add_attribute("applicant", applicant)
+        .add_attribute("rent", rent))
+}
+
+fn execute_withdraw_offer(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let key = (U64Key::from(id), &info.sender);
+    let offer = offers()
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoOffer {})?;
+    offers().remove(deps.storage, key)?;
+    sub_total(deps.storage, &ESCROW_TOTAL, offer.escrow)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_offer")
+        .add_attribute("id", id.to_string())
+        .add_message(send_acudos(info.sender, offer.escrow)))
+}
+
+// loads the offer of the applicant, failing once it has expired
+fn load_open_offer(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    id: u64,
+    applicant: &Addr,
+) -> Result<Offer, ContractError> {
+    let offer = offers()
+        .may_load(storage, (U64Key::from(id), applicant))?
+        .ok_or(ContractError::NoOffer {})?;
+    if offer.expires.is_expired(block) {
+        return Err(ContractError::OfferExpired {});
+    }
+    Ok(offer)
+}
+
+fn execute_set_daily_rate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    daily_rate: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // Only the renter or a manager allowed to reprice sets the rate of booked stays.
+    let mut property = load_property(deps.storage, id)?;
+    assert_can_manage(
+        deps.storage,
+        &env.block,
+        id,
+        &property,
+        &info.sender,
+        Permission::Reprice,
+    )?;
+    if daily_rate.is_some_and(|rate| rate.is_zero()) {
+        return Err(ContractError::InvalidDailyRate {});
+    }
 
-    if id > cfg_flatlist.len() - 1 {
-        return Err(ContractError::NotFound {});
+    property.daily_rate = daily_rate;
+    save_property(deps.storage, id, &property, env.block.height)?;
+
+    let daily_rate = daily_rate.map_or_else(|| String::from("none"), |r| r.to_string());
+    Ok(Response::new()
+        .add_attribute("action", "set_daily_rate")
+        .add_attribute("id", id.to_string())
+        .add_attribute("daily_rate", daily_rate))
+}
+
+fn execute_book_property(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    start: Expiration,
+    end: Expiration,
+) -> Result<Response, ContractError> {
+    // Stays are booked in advance on properties without a lease, at the daily rate of the
+    // property. The escrow is held until the stay is over, funds beyond it are refunded.
+    let property = load_property(deps.storage, id)?;
+    if property.renter == info.sender.as_str() {
+        return Err(ContractError::InvalidRentee {});
+    }
+    assert_not_suspended(deps.storage, &property)?;
+    if property.rentee.is_some() {
+        return Err(ContractError::RenteeExist {});
+    }
+    let daily_rate = property
+        .daily_rate
+        .ok_or(ContractError::BookingsClosed {})?;
+
+    let (start_time, end_time) = match (time_of(&start), time_of(&end)) {
+        (Some(start), Some(end)) if start >= env.block.time && end > start => (start, end),
+        _ => return Err(ContractError::InvalidBooking {}),
     };
+    if is_booked(deps.storage, id, start_time, end_time)? {
+        return Err(ContractError::BookingOverlap {});
+    }
 
-    if cfg_flatlist[id].renter != info.sender.to_string() {
-        return Err(ContractError::InvalidRenter {});
+    let price = stay_price(daily_rate, start_time, end_time)?;
+    let denom = DENOM.load(deps.storage)?;
+    let paid = info
+        .funds
+        .iter()
+        .find(|x| x.amount >= price && x.denom == denom)
+        .map(|x| x.amount)
+        .ok_or(ContractError::LessThanRent {})?;
+
+    let booking = Booking {
+        guest: info.sender.clone(),
+        start,
+        end,
+        escrow: price,
     };
+    BOOKINGS.save(deps.storage, booking_key(id, start_time), &booking)?;
+    add_total(deps.storage, &ESCROW_TOTAL, booking.escrow)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "book_property")
+        .add_attribute("id", id.to_string())
+        .add_attribute("start", start.to_string())
+        .add_attribute("end", end.to_string())
+        .add_attribute("escrow", price);
+    if paid > price {
+        res = res.add_message(send_acudos(info.sender, paid - price));
+    }
+    Ok(res)
+}
 
-    if cfg_flatlist[id].expires == None {
+fn execute_cancel_booking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    start: Expiration,
+) -> Result<Response, ContractError> {
+    // The guest, or the renter and its managers allowed to reject, cancel before the stay
+    // begins. The guest gets the whole escrow back.
+    let key = booking_key(id, time_of(&start).ok_or(ContractError::NoBooking {})?);
+    let booking = BOOKINGS
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoBooking {})?;
+    if booking.guest != info.sender {
+        let property = load_property(deps.storage, id)?;
+        assert_can_manage(
+            deps.storage,
+            &env.block,
+            id,
+            &property,
+            &info.sender,
+            Permission::AcceptReject,
+        )?;
+    }
+    if booking.start.is_expired(&env.block) {
+        return Err(ContractError::BookingStarted {});
+    }
+    BOOKINGS.remove(deps.storage, key);
+    sub_total(deps.storage, &ESCROW_TOTAL, booking.escrow)?;
+
+    let refund = payout(deps.storage, &env.block, &booking.guest, booking.escrow)?;
+    Ok(Response::new()
+        .add_attribute("action", "cancel_booking")
+        .add_attribute("id", id.to_string())
+        .add_attribute("guest", booking.guest)
+        .add_submessage(refund))
+}
+
+fn execute_close_booking(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+    start: Expiration,
+) -> Result<Response, ContractError> {
+    // Once the stay is over its escrow is rent of the renter, released like any other rent.
+    let key = booking_key(id, time_of(&start).ok_or(ContractError::NoBooking {})?);
+    let booking = BOOKINGS
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoBooking {})?;
+    if !booking.end.is_expired(&env.block) {
+        return Err(ContractError::BookingNotOver {});
+    }
+    BOOKINGS.remove(deps.storage, key);
+    sub_total(deps.storage, &ESCROW_TOTAL, booking.escrow)?;
+
+    let property = load_property(deps.storage, id)?;
+    let config = CONFIG.load(deps.storage)?;
+    record_payment(
+        deps.storage,
+        booking.guest.as_str(),
+        &property.renter,
+        booking.escrow,
+    )?;
+    let rent = pay_renter(
+        deps.storage,
+        &env.block,
+        &config,
+        &property.renter,
+        booking.escrow,
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "close_booking")
+        .add_attribute("id", id.to_string())
+        .add_attribute("rent", booking.escrow)
+        .add_submessages(rent))
+}
+
+fn execute_terminate_lease(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // Can be called by Renter of the property and is used to terminate the lease only if Rentee defaults on any month rent,
+    // ie. did not pay it within the grace window.
+    // A fixed term lease past its end is settled like ClaimDeposit, as ended if paid up to it.
+    // Release the security deposit to rentee.
+    // Update the expiration date with None
+    // Remove the rentee with that property id.
+    let mut property = load_property(deps.storage, id)?;
+
+    assert_can_manage(
+        deps.storage,
+        &env.block,
+        id,
+        &property,
+        &info.sender,
+        Permission::Terminate,
+    )?;
+
+    if property.expires.is_none() {
         return Err(ContractError::IsNotRented {});
     };
 
-    let exp = cfg_flatlist[id].expires.unwrap().is_expired(&env.block);
-    if !exp {
+    // the rentee may still pay late within the grace window
+    let ended = property
+        .ends
+        .is_some_and(|ends| ends.is_expired(&env.block));
+    let lease_period = lease_period(&property, &CONFIG.load(deps.storage)?);
+    if !ended && !in_default(&property, &env.block, lease_period) {
         return Err(ContractError::NotExpired {});
     }
 
-    let codocrypto = Coin {
-        amount: cfg_flatlist[id].rent,
-        denom: String::from("acudos"),
+    let msgs = match ended {
+        true => settle_term(deps.storage, &deps.querier, &env, id, &mut property)?,
+        false => settle_default(deps.storage, &deps.querier, &env, id, &mut property)?,
     };
 
-    BankMsg::Send {
-        to_address: cfg_flatlist[id].rentee.as_ref().unwrap().to_string(),
-        amount: vec![codocrypto],
+    save_property(deps.storage, id, &property, env.block.height)?;
+    Ok(Response::new()
+        .add_attribute("action", "terminate_lease")
+        .add_attribute("id", id.to_string())
+        .add_submessages(msgs))
+}
+
+// Closes a lease whose rentee defaulted on rent: the deposit goes back to the rentee, the
+// termination is recorded against it and hooks are told. Returns the messages to send,
+// the caller saves the property.
+fn settle_default(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+    property: &mut FlatInfo,
+) -> StdResult<Vec<SubMsg>> {
+    let rentee = property.rentee.clone().unwrap_or_default();
+    let mut msgs = release_deposit(storage, querier, env, id, property)?;
+    let hooks = lease_changed_hooks(storage, id, property, LeaseEvent::Defaulted)?;
+    update_reputation(storage, &rentee, |r| r.terminations_for_default += 1)?;
+    property.clear_lease();
+
+    msgs.extend(hooks);
+    Ok(msgs)
+}
+
+// Closes a fixed term lease that ran until its end: the deposit goes back to the rentee,
+// the lease counts as completed for both parties and hooks are told. Returns the messages
+// to send, the caller saves the property.
+fn settle_ended(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+    property: &mut FlatInfo,
+) -> StdResult<Vec<SubMsg>> {
+    let mut msgs = release_deposit(storage, querier, env, id, property)?;
+    let hooks = lease_changed_hooks(storage, id, property, LeaseEvent::Ended)?;
+    complete_lease(storage, property)?;
+    property.clear_lease();
+
+    msgs.extend(hooks);
+    Ok(msgs)
+}
+
+// Closes a fixed term lease whose end was reached, as ended if its rent was paid up to the
+// end and as defaulted otherwise. Returns the messages to send, the caller saves the property.
+fn settle_term(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+    property: &mut FlatInfo,
+) -> StdResult<Vec<SubMsg>> {
+    match fully_paid(property) {
+        true => settle_ended(storage, querier, env, id, property),
+        false => settle_default(storage, querier, env, id, property),
+    }
+}
+
+// Returns the deposit of a lease being closed to the rentee. A staked deposit is undelegated
+// and claimable after unbonding, its rewards are paid to the rentee and the renter right away.
+fn release_deposit(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+    property: &FlatInfo,
+) -> StdResult<Vec<SubMsg>> {
+    let rentee = property.rentee.clone().unwrap_or_default();
+    let unstaked = match unstake_deposit(storage, querier, env, id, &rentee)? {
+        Some(unstaked) => unstaked,
+        // an emergency withdrawal may have taken the escrow already
+        None if property.escrow.is_zero() => return Ok(vec![]),
+        None => return Ok(vec![payout(storage, &env.block, rentee, property.escrow)?]),
     };
+    let mut msgs = unstaked.msgs;
+    for (recipient, rewards) in [
+        (rentee.as_str(), unstaked.rentee_rewards),
+        (property.renter.as_str(), unstaked.renter_rewards),
+    ] {
+        if !rewards.is_zero() {
+            msgs.push(payout(storage, &env.block, recipient, rewards)?);
+        }
+    }
+    Ok(msgs)
+}
 
-    cfg_flatlist[id].expires = None;
-    cfg_flatlist[id].rentee = None;
+fn execute_renew_lease(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    term: LeaseTerm,
+) -> Result<Response, ContractError> {
+    // Extends a fixed term lease that has not ended yet, both parties must sign off on it.
+    // The renter offers the renewal term first, then the rentee accepts it by sending the same term.
+    // A renewal in periods is counted from the end of the current term.
+    let mut property = load_property(deps.storage, id)?;
 
-    FLAT_LIST.save(deps.storage, &cfg_flatlist)?;
-    Ok(Response::default())
+    if property.expires.is_none() {
+        return Err(ContractError::IsNotRented {});
+    }
+
+    let ends = property.ends.ok_or(ContractError::NoLeaseTerm {})?;
+    if ends.is_expired(&env.block) {
+        return Err(ContractError::LeaseEnded {});
+    }
+
+    validate_term(&term, &env.block)?;
+    // a renewal has to end in the unit of the current term
+    let lease_period = lease_period(&property, &CONFIG.load(deps.storage)?);
+    let new_end = term_end(&term, ends, lease_period)?;
+    if new_end.partial_cmp(&ends) != Some(std::cmp::Ordering::Greater) {
+        return Err(ContractError::InvalidTerm {});
+    }
+
+    let mut hooks = vec![];
+    let action = if property.renter == info.sender.as_str() {
+        property.renewal = Some(term);
+        "offer_renewal"
+    } else if property.rentee.as_deref() == Some(info.sender.as_str()) {
+        match &property.renewal {
+            None => return Err(ContractError::NoRenewalOffer {}),
+            Some(offer) if offer != &term => return Err(ContractError::RenewalMismatch {}),
+            Some(_) => {}
+        }
+        property.ends = Some(new_end);
+        property.term = Some(term);
+        property.renewal = None;
+        hooks = lease_changed_hooks(deps.storage, id, &property, LeaseEvent::Renewed)?;
+        "renew_lease"
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    save_property(deps.storage, id, &property, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("id", id.to_string())
+        .add_submessages(hooks))
+}
+
+fn execute_claim_deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // Once a fixed term lease has ended, the lease is closed and the rentee gets the deposit back.
+    // If the rent was not paid up to the end, the lease is closed as defaulted.
+    let mut property = load_property(deps.storage, id)?;
+
+    if property.rentee.as_deref() != Some(info.sender.as_str()) {
+        return Err(ContractError::InvalidRentee {});
+    }
+
+    let ends = property.ends.ok_or(ContractError::NoLeaseTerm {})?;
+    if !ends.is_expired(&env.block) {
+        return Err(ContractError::NotExpired {});
+    }
+
+    let msgs = settle_term(deps.storage, &deps.querier, &env, id, &mut property)?;
+
+    save_property(deps.storage, id, &property, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_deposit")
+        .add_attribute("id", id.to_string())
+        .add_submessages(msgs))
+}
+
+fn execute_record_dispute_lost(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    loser: String,
+) -> Result<Response, ContractError> {
+    // The admin settles disputes between renter and rentee outside of the contract
+    // and records the outcome on the reputation of the losing party.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let property = load_property(deps.storage, id)?;
+
+    if property.renter != loser && property.rentee.as_deref() != Some(loser.as_str()) {
+        return Err(ContractError::NotAParty {});
+    }
+
+    update_reputation(deps.storage, &loser, |r| r.disputes_lost += 1)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "record_dispute_lost")
+        .add_attribute("id", id.to_string())
+        .add_attribute("loser", loser))
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    lease_period: Option<u64>,
+    lease_period_seconds: Option<u64>,
+    max_batch_size: Option<u32>,
+    pull_rent: Option<bool>,
+) -> Result<Response, ContractError> {
+    // Platform parameters are governed by the admin. A new lease period applies to leases
+    // accepted afterwards, running leases keep the period they were accepted with. Rent
+    // accrued before pull_rent is switched off stays withdrawable.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if let Some(lease_period) = lease_period {
+        if lease_period == 0 {
+            return Err(ContractError::InvalidLeasePeriod {});
+        }
+        config.lease_period = lease_period;
+    }
+    if let Some(lease_period_seconds) = lease_period_seconds {
+        if lease_period_seconds == 0 {
+            return Err(ContractError::InvalidLeasePeriod {});
+        }
+        config.lease_period_seconds = lease_period_seconds;
+    }
+    if let Some(max_batch_size) = max_batch_size {
+        if max_batch_size == 0 {
+            return Err(ContractError::InvalidBatchSize {});
+        }
+        config.max_batch_size = max_batch_size;
+    }
+    if let Some(pull_rent) = pull_rent {
+        config.pull_rent = pull_rent;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("lease_period", config.lease_period.to_string())
+        .add_attribute(
+            "lease_period_seconds",
+            config.lease_period_seconds.to_string(),
+        )
+        .add_attribute("max_batch_size", config.max_batch_size.to_string())
+        .add_attribute("pull_rent", config.pull_rent.to_string()))
+}
+
+fn execute_update_landlord_group(
+    deps: DepsMut,
+    info: MessageInfo,
+    landlord_group: Option<String>,
+) -> Result<Response, ContractError> {
+    // Listings made before the group was set stay as they are, they are only suspended
+    // once the group reports their renter leaving.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.landlord_group = landlord_group
+        .map(|addr| validate_group(deps.as_ref(), addr))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let group = config
+        .landlord_group
+        .map_or_else(|| String::from("none"), |g| g.addr().to_string());
+    Ok(Response::new()
+        .add_attribute("action", "update_landlord_group")
+        .add_attribute("landlord_group", group))
+}
+
+fn execute_update_insurance(
+    deps: DepsMut,
+    info: MessageInfo,
+    insurance: Option<InsuranceInfo>,
+) -> Result<Response, ContractError> {
+    // The premium applies from the next rent payment on. The pool also has to be added
+    // as a hook to learn about defaults.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let insurance = insurance
+        .map(|info| -> Result<_, ContractError> {
+            if info.premium.is_zero() || info.premium >= Decimal::one() {
+                return Err(ContractError::InvalidPremium {});
+            }
+            Ok(Insurance {
+                pool: deps.api.addr_validate(&info.pool)?,
+                premium: info.premium,
+            })
+        })
+        .transpose()?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.insurance = insurance;
+    CONFIG.save(deps.storage, &config)?;
+
+    let pool = config
+        .insurance
+        .map_or_else(|| String::from("none"), |i| i.pool.to_string());
+    Ok(Response::new()
+        .add_attribute("action", "update_insurance")
+        .add_attribute("pool", pool))
+}
+
+fn execute_update_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> Result<Response, ContractError> {
+    // Without an oracle, rents in a reference currency cannot be paid until one is set again.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.oracle = oracle.map(|o| deps.api.addr_validate(&o)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let oracle = config
+        .oracle
+        .map_or_else(|| String::from("none"), String::from);
+    Ok(Response::new()
+        .add_attribute("action", "update_oracle")
+        .add_attribute("oracle", oracle))
+}
+
+fn execute_update_staking(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking: Option<DepositStaking>,
+) -> Result<Response, ContractError> {
+    // Applies to leases accepted from now on, deposits already delegated stay with their
+    // validator until their lease is closed.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    if let Some(staking) = &staking {
+        if staking.rentee_share > Decimal::one() {
+            return Err(ContractError::InvalidRewardShare {});
+        }
+        if deps.querier.query_validator(&staking.validator)?.is_none() {
+            return Err(ContractError::UnknownValidator {
+                validator: staking.validator.clone(),
+            });
+        }
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.staking = staking;
+    CONFIG.save(deps.storage, &config)?;
+
+    let validator = config
+        .staking
+        .map_or_else(|| String::from("none"), |s| s.validator);
+    Ok(Response::new()
+        .add_attribute("action", "update_staking")
+        .add_attribute("validator", validator))
+}
+
+fn execute_grant_manager(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    manager: String,
+    property_id: Option<u64>,
+    permissions: Vec<Permission>,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    // A renter lets a manager accept or reject rentees, terminate leases or re-price
+    // listings on its behalf. Funds still go to the renter.
+    let manager = deps.api.addr_validate(&manager)?;
+    let expires = expires.unwrap_or_default();
+    if permissions.is_empty() || expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidGrant {});
+    }
+    let grant = Grant {
+        permissions,
+        expires,
+    };
+
+    match property_id {
+        Some(id) => {
+            let property = load_property(deps.storage, id)?;
+            if property.renter != info.sender.as_str() {
+                return Err(ContractError::InvalidRenter {});
+            }
+            PROPERTY_MANAGERS.save(
+                deps.storage,
+                (&info.sender, &manager, U64Key::from(id)),
+                &grant,
+            )?;
+        }
+        None => RENTER_MANAGERS.save(deps.storage, (&info.sender, &manager), &grant)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_manager")
+        .add_attribute("manager", manager)
+        .add_attribute("scope", scope(property_id)))
+}
+
+fn execute_revoke_manager(
+    deps: DepsMut,
+    info: MessageInfo,
+    manager: String,
+    property_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let manager = deps.api.addr_validate(&manager)?;
+    match property_id {
+        Some(id) => {
+            let key = (&info.sender, &manager, U64Key::from(id));
+            if !PROPERTY_MANAGERS.has(deps.storage, key.clone()) {
+                return Err(ContractError::NoGrant {});
+            }
+            PROPERTY_MANAGERS.remove(deps.storage, key);
+        }
+        None => {
+            if !RENTER_MANAGERS.has(deps.storage, (&info.sender, &manager)) {
+                return Err(ContractError::NoGrant {});
+            }
+            RENTER_MANAGERS.remove(deps.storage, (&info.sender, &manager));
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_manager")
+        .add_attribute("manager", manager)
+        .add_attribute("scope", scope(property_id)))
+}
+
+// attribute value for the properties a grant covers
+fn scope(property_id: Option<u64>) -> String {
+    property_id.map_or_else(|| String::from("all"), |id| id.to_string())
+}
+
+fn execute_claim_payout(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Pulled with a plain transfer, if it fails the amount stays claimable.
+    let amount = CLAIMABLE_PAYOUTS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    CLAIMABLE_PAYOUTS.remove(deps.storage, &info.sender);
+    sub_total(deps.storage, &CLAIMABLE_TOTAL, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_payout")
+        .add_attribute("amount", amount)
+        .add_message(send_acudos(info.sender, amount)))
+}
+
+fn execute_claim_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Deposits still unbonding stay claimable for later.
+    let amount = UNBONDING.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingUnbonded {});
+    }
+    sub_total(deps.storage, &UNBONDING_TOTAL, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_unbonded")
+        .add_attribute("amount", amount)
+        .add_message(send_acudos(info.sender, amount)))
+}
+
+fn execute_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Option<Vec<Coin>>,
+) -> Result<Response, ContractError> {
+    // Without an amount the whole accrued rent is sent, what is left stays withdrawable.
+    let balance = RENTER_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let mut amount = NativeBalance(amount.unwrap_or_else(|| balance.clone().into_vec()));
+    amount.normalize();
+    if amount.is_empty() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    let mut remaining = balance;
+    for coin in amount.clone().into_vec() {
+        remaining = (remaining - coin).map_err(|_| ContractError::InsufficientBalance {})?;
+    }
+    if remaining.is_empty() {
+        RENTER_BALANCES.remove(deps.storage, &info.sender);
+    } else {
+        RENTER_BALANCES.save(deps.storage, &info.sender, &remaining)?;
+    }
+    let acudos = amount.0.iter().find(|c| c.denom == "acudos");
+    sub_total(
+        deps.storage,
+        &ACCRUED_TOTAL,
+        acudos.map_or(Uint128::zero(), |c| c.amount),
+    )?;
+
+    let amount = amount.into_vec();
+    let attribute = amount
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(Response::new()
+        .add_attribute("action", "withdraw")
+        .add_attribute("amount", attribute)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.into(),
+            amount,
+        }))
+}
+
+fn execute_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    actions: Option<Vec<LeaseAction>>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    match actions {
+        None => pause.all = true,
+        Some(actions) => {
+            for action in actions {
+                if !pause.actions.contains(&action) {
+                    pause.actions.push(action);
+                }
+            }
+        }
+    }
+    PAUSE.save(deps.storage, &pause)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("all", pause.all.to_string()))
+}
+
+fn execute_unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+    actions: Option<Vec<LeaseAction>>,
+) -> Result<Response, ContractError> {
+    // Resuming single actions only lifts their own pause, not the pause of everything.
+    // A started emergency withdrawal has to be finished first.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    let pause = match actions {
+        None if WITHDRAWAL.may_load(deps.storage)?.is_some() => {
+            return Err(ContractError::WithdrawalInProgress {})
+        }
+        None => PauseState::default(),
+        Some(actions) => {
+            let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+            pause.actions.retain(|a| !actions.contains(a));
+            pause
+        }
+    };
+    PAUSE.save(deps.storage, &pause)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("all", pause.all.to_string()))
+}
+
+fn execute_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Returns the escrow of every property to its rentee. If the contract holds less acudos
+    // than the escrows add up to, every rentee gets the same share of its escrow. Pending
+    // requests, offers and bookings are closed, accepted leases go on without a deposit. Staked
+    // deposits are undelegated in full and claimable after unbonding.
+    // The escrows and funds are counted once by the first call, every call then handles a
+    // batch of escrows like ProcessExpired, until all of them are refunded.
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    if !PAUSE.may_load(deps.storage)?.unwrap_or_default().all {
+        return Err(ContractError::NotPaused {});
+    }
+    let max_batch_size = CONFIG.load(deps.storage)?.max_batch_size;
+    let mut limit = limit.unwrap_or(max_batch_size).min(max_batch_size) as usize;
+
+    let mut withdrawal = match WITHDRAWAL.may_load(deps.storage)? {
+        Some(withdrawal) => withdrawal,
+        None => start_withdrawal(deps.as_ref(), &env)?,
+    };
+    let Withdrawal {
+        total, available, ..
+    } = withdrawal;
+    let mut res = Response::new()
+        .add_attribute("action", "emergency_withdraw")
+        .add_attribute("escrow", total)
+        .add_attribute("available", available);
+
+    while limit > 0 {
+        let start = withdrawal.cursor.clone().map(Bound::exclusive);
+        let keys = match withdrawal.kind {
+            EscrowKind::Property => {
+                let batch = properties()
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(limit)
+                    .collect::<StdResult<Vec<_>>>()?;
+                let keys: Vec<_> = batch.iter().map(|(key, _)| key.clone()).collect();
+                for (key, mut property) in batch {
+                    if property.escrow.is_zero() {
+                        continue;
+                    }
+                    let id = parse_id(&key)?;
+                    if STAKES.has(deps.storage, U64Key::from(id)) {
+                        let msgs =
+                            release_deposit(deps.storage, &deps.querier, &env, id, &property)?;
+                        property.escrow = Uint128::zero();
+                        res = res
+                            .add_submessages(msgs)
+                            .add_attribute("unstake", id.to_string());
+                    } else {
+                        let refund = property.escrow.multiply_ratio(available, total);
+                        if let (Some(rentee), false) = (&property.rentee, refund.is_zero()) {
+                            res = res.add_submessage(payout(
+                                deps.storage,
+                                &env.block,
+                                rentee,
+                                refund,
+                            )?);
+                        }
+                        if property.expires.is_none() {
+                            property.clear_lease();
+                        } else {
+                            property.escrow = Uint128::zero();
+                        }
+                        res = res.add_attribute("refund", id.to_string());
+                    }
+                    save_property(deps.storage, id, &property, env.block.height)?;
+                }
+                keys
+            }
+            EscrowKind::Offer => {
+                let batch = offers()
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(limit)
+                    .collect::<StdResult<Vec<_>>>()?;
+                let keys: Vec<_> = batch.iter().map(|(key, _)| key.clone()).collect();
+                for (key, offer) in batch {
+                    let (id, applicant) = parse_offer_key(&key)?;
+                    let refund = offer.escrow.multiply_ratio(available, total);
+                    if !refund.is_zero() {
+                        res = res.add_submessage(payout(
+                            deps.storage,
+                            &env.block,
+                            &applicant,
+                            refund,
+                        )?);
+                    }
+                    offers().remove(deps.storage, (U64Key::from(id), &applicant))?;
+                    sub_total(deps.storage, &ESCROW_TOTAL, offer.escrow)?;
+                }
+                keys
+            }
+            EscrowKind::Booking => {
+                let batch = BOOKINGS
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(limit)
+                    .collect::<StdResult<Vec<_>>>()?;
+                let keys: Vec<_> = batch.iter().map(|(key, _)| key.clone()).collect();
+                for (key, booking) in batch {
+                    // the id is prefixed with its length
+                    let id = parse_id(key.get(2..).unwrap_or_default())?;
+                    let refund = booking.escrow.multiply_ratio(available, total);
+                    if !refund.is_zero() {
+                        res = res.add_submessage(payout(
+                            deps.storage,
+                            &env.block,
+                            &booking.guest,
+                            refund,
+                        )?);
+                    }
+                    let start = time_of(&booking.start).unwrap_or_default();
+                    BOOKINGS.remove(deps.storage, booking_key(id, start));
+                    sub_total(deps.storage, &ESCROW_TOTAL, booking.escrow)?;
+                }
+                keys
+            }
+        };
+        limit -= keys.len();
+        // a short batch finished the escrows of this kind, the next kind starts from its first
+        if limit > 0 {
+            withdrawal.cursor = None;
+            withdrawal.kind = match withdrawal.kind {
+                EscrowKind::Property => EscrowKind::Offer,
+                EscrowKind::Offer => EscrowKind::Booking,
+                EscrowKind::Booking => {
+                    WITHDRAWAL.remove(deps.storage);
+                    return Ok(res.add_attribute("done", "true"));
+                }
+            };
+        } else {
+            withdrawal.cursor = keys.last().cloned();
+        }
+    }
+    WITHDRAWAL.save(deps.storage, &withdrawal)?;
+    Ok(res.add_attribute("done", "false"))
+}
+
+// Counts what is escrowed and what the contract holds for it when an emergency withdrawal
+// starts, every escrow is refunded at this ratio
+fn start_withdrawal(deps: Deps, env: &Env) -> StdResult<Withdrawal> {
+    // staked deposits are not held by the contract, they are undelegated in full
+    let total = load_total(deps.storage, &ESCROW_TOTAL)?
+        .saturating_sub(load_total(deps.storage, &STAKED_TOTAL)?);
+    // acudos of failed payouts, accrued rent, staking rewards and undelegated deposits are
+    // owed to their recipients, not to the rentees
+    let claimable = load_total(deps.storage, &CLAIMABLE_TOTAL)?;
+    let accrued = load_total(deps.storage, &ACCRUED_TOTAL)?;
+    let rewards = held_rewards(deps.storage)?;
+    let unbonding = unbonding_total(deps.storage)?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, "acudos")?
+        .amount;
+    let available = balance
+        .saturating_sub(claimable)
+        .saturating_sub(accrued)
+        .saturating_sub(rewards)
+        .saturating_sub(unbonding)
+        .min(total);
+    Ok(Withdrawal {
+        total,
+        available,
+        kind: EscrowKind::Property,
+        cursor: None,
+    })
+}
+
+fn execute_member_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MemberChangedHookMsg,
+) -> Result<Response, ContractError> {
+    // Only the configured landlord group reports member changes. Landlords whose weight
+    // drops to zero get their listings suspended, pending requests on them can no longer
+    // be accepted and their rentees withdraw them. Running leases go on. Listings are
+    // reinstated when the landlord is a member with weight again. Only the landlords are
+    // recorded, so the work does not grow with the number of their listings.
+    let group = CONFIG
+        .load(deps.storage)?
+        .landlord_group
+        .ok_or(ContractError::Unauthorized {})?;
+    if info.sender != group.addr() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut res = Response::new().add_attribute("action", "member_changed");
+    for diff in msg.diffs {
+        let renter = Addr::unchecked(diff.key);
+        let suspended = SUSPENDED_LANDLORDS.has(deps.storage, &renter);
+        if diff.new.is_none_or(|w| w == 0) {
+            if !suspended {
+                SUSPENDED_LANDLORDS.save(deps.storage, &renter, &env.block.height)?;
+                res = res.add_attribute("suspended", renter);
+            }
+        } else if suspended {
+            SUSPENDED_LANDLORDS.remove(deps.storage, &renter);
+            res = res.add_attribute("reinstated", renter);
+        }
+    }
+    Ok(res)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, _info: MessageInfo, msg: QueryMsg) -> StdResult<Binary> {
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
     match msg {
-        QueryMsg::PropertyDetail(id) => to_binary(&query_property_info(deps, id)?),
-        QueryMsg::ShowAllAvailableProperties => {
-            to_binary(&query_show_all_available_properties(deps)?)
+        SudoMsg::ProcessExpired { limit } => sudo_process_expired(deps, env, limit),
+    }
+}
+
+fn sudo_process_expired(deps: DepsMut, env: Env, limit: u32) -> Result<Response, ContractError> {
+    // Settles what TerminateLease and ClaimDeposit would: leases whose rent is overdue beyond
+    // the grace window are terminated for default, fixed term leases are closed once they
+    // reached their end, as ended if paid up to it and as defaulted otherwise. Nothing is
+    // swept while the whole contract is paused.
+    let res = Response::new().add_attribute("action", "process_expired");
+    if PAUSE.may_load(deps.storage)?.unwrap_or_default().all {
+        return Ok(res.add_attribute("paused", "true"));
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.min(config.max_batch_size) as usize;
+    let start = SWEEP_CURSOR.may_load(deps.storage)?.unwrap_or_default();
+
+    let batch = properties()
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(U64Key::from(start))),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (key, property) = item?;
+            Ok((parse_id(&key)?, property))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    // a short batch reached the last property, the next sweep starts over
+    let next = match batch.last() {
+        Some((id, _)) if batch.len() == limit => id + 1,
+        _ => 0,
+    };
+    SWEEP_CURSOR.save(deps.storage, &next)?;
+
+    let mut res = res
+        .add_attribute("scanned", batch.len().to_string())
+        .add_attribute("next", next.to_string());
+    for (id, mut property) in batch {
+        if property.expires.is_none() {
+            continue;
+        }
+        let ended = property
+            .ends
+            .is_some_and(|ends| ends.is_expired(&env.block));
+        let lease_period = lease_period(&property, &config);
+        if !ended && !in_default(&property, &env.block, lease_period) {
+            continue;
         }
-        QueryMsg::GetTotalProperties => to_binary(&query_get_total_property(deps)?),
+        let event = Event::new("lease_expired")
+            .add_attribute("id", id.to_string())
+            .add_attribute("rentee", property.rentee.clone().unwrap_or_default())
+            .add_attribute("refund", property.escrow);
+        let (outcome, msgs) = match ended && fully_paid(&property) {
+            true => (
+                "ended",
+                settle_ended(deps.storage, &deps.querier, &env, id, &mut property)?,
+            ),
+            false => (
+                "defaulted",
+                settle_default(deps.storage, &deps.querier, &env, id, &mut property)?,
+            ),
+        };
+        save_property(deps.storage, id, &property, env.block.height)?;
+        res = res
+            .add_event(event.add_attribute("outcome", outcome))
+            .add_submessages(msgs);
     }
+    Ok(res)
 }
 
-pub fn query_property_info(deps: Deps, id: usize) -> StdResult<FlatInfo> {
-    let flat_list = FLAT_LIST.may_load(deps.storage)?.unwrap();
-    if id > flat_list.len() {
-        return Err(StdError::NotFound {
-            kind: String::from("property not found"),
-        });
+/// Only failed payouts reply, their amount becomes claimable by the recipient
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let key = U64Key::from(msg.id);
+    let pending = PENDING_PAYOUTS.load(deps.storage, key.clone())?;
+    PENDING_PAYOUTS.remove(deps.storage, key);
+
+    let error = match msg.result {
+        ContractResult::Err(error) => error,
+        ContractResult::Ok(_) => return Ok(Response::default()),
+    };
+    CLAIMABLE_PAYOUTS.update(
+        deps.storage,
+        &pending.recipient,
+        |claimable| -> StdResult<_> { Ok(claimable.unwrap_or_default() + pending.amount) },
+    )?;
+    add_total(deps.storage, &CLAIMABLE_TOTAL, pending.amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "payout_failed")
+        .add_attribute("recipient", pending.recipient)
+        .add_attribute("amount", pending.amount)
+        .add_attribute("error", error))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::PropertyDetail(id) => {
+            let property = query_property_info(deps, id)?;
+            to_binary(&PropertyResponse {
+                suspended: is_suspended(deps.storage, &property.renter),
+                property,
+            })
+        }
+        QueryMsg::PropertyAtHeight {
+            property_id,
+            height,
+        } => to_binary(&PropertyAtHeightResponse {
+            property: query_property_at_height(deps, property_id, height)?,
+        }),
+        QueryMsg::LeaseStatus { property_id } => {
+            to_binary(&query_lease_status(deps, env, property_id)?)
+        }
+        QueryMsg::ShowAllAvailableProperties => to_binary(&AvailablePropertiesResponse {
+            properties: query_show_all_available_properties(deps)?,
+        }),
+        QueryMsg::GetTotalProperties => to_binary(&TotalPropertiesResponse {
+            total: query_get_total_property(deps)?,
+        }),
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::Reputation { address } => to_binary(&query_reputation(deps, address)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::RentQuote { property_id } => {
+            to_binary(&query_rent_quote(deps, env, property_id)?)
+        }
+        QueryMsg::Permissions { renter, manager } => {
+            to_binary(&query_permissions(deps, env, renter, manager)?)
+        }
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::ClaimablePayout { address } => to_binary(&query_claimable_payout(deps, address)?),
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::TenantOverview {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query_tenant_overview(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::LandlordOverview {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query_landlord_overview(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::SearchProperties {
+            min_rent,
+            max_rent,
+            denom,
+            renter,
+            start_after,
+            limit,
+        } => to_binary(&query_search_properties(
+            deps,
+            min_rent,
+            max_rent,
+            denom,
+            renter,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Offers {
+            property_id,
+            start_after,
+            limit,
+        } => to_binary(&query_offers(deps, property_id, start_after, limit)?),
+        QueryMsg::Bookings {
+            property_id,
+            start_after,
+            limit,
+        } => to_binary(&query_bookings(deps, property_id, start_after, limit)?),
+        QueryMsg::Availability {
+            property_id,
+            from,
+            until,
+            limit,
+        } => to_binary(&query_availability(
+            deps,
+            env,
+            property_id,
+            from,
+            until,
+            limit,
+        )?),
+        QueryMsg::Claims { address } => {
+            to_binary(&UNBONDING.query_claims(deps, &deps.api.addr_validate(&address)?)?)
+        }
+        QueryMsg::DepositStake { property_id } => {
+            to_binary(&query_deposit_stake(deps, env, property_id)?)
+        }
     }
-    Ok(flat_list[id].clone())
 }
 
-pub fn query_show_all_available_properties(deps: Deps) -> StdResult<Vec<usize>> {
-    let mut flat_list = vec![];
-    let flat_list2 = FLAT_LIST.may_load(deps.storage)?.unwrap();
-    flat_list.push(flat_list2.len());
-    Ok(flat_list)
+pub fn query_property_info(deps: Deps, id: u64) -> StdResult<FlatInfo> {
+    properties()
+        .may_load(deps.storage, U64Key::from(id))?
+        .ok_or_else(property_not_found)
 }
 
-pub fn query_get_total_property(deps: Deps) -> StdResult<usize> {
-    let flat_list2 = FLAT_LIST.may_load(deps.storage)?.unwrap();
-    Ok(flat_list2.len())
+/// Returns the property as it was at the beginning of block `height`,
+/// or None if it was not listed yet at that point.
+pub fn query_property_at_height(deps: Deps, id: u64, height: u64) -> StdResult<Option<FlatInfo>> {
+    if id >= query_get_total_property(deps)? {
+        return Err(property_not_found());
+    }
+    properties().may_load_at_height(deps.storage, U64Key::from(id), height)
+}
+
+pub fn query_lease_status(deps: Deps, env: Env, id: u64) -> StdResult<LeaseStatusResponse> {
+    let property = query_property_info(deps, id)?;
+    let lease_period = lease_period(&property, &CONFIG.load(deps.storage)?);
+    let rent_due = property
+        .expires
+        .map(|_| rent_due(&property, &env.block, lease_period))
+        .transpose()?;
+    let ended = property
+        .ends
+        .is_some_and(|ends| ends.is_expired(&env.block));
+    let grace_ends = grace_end(&property, lease_period);
+    Ok(LeaseStatusResponse {
+        rentee: property.rentee,
+        expires: property.expires,
+        rent_due,
+        rent_schedule: property.rent_schedule,
+        grace_ends,
+        ends: property.ends,
+        ended,
+        renewal: property.renewal,
+        document: property.document,
+    })
+}
+
+pub fn query_rent_quote(deps: Deps, env: Env, id: u64) -> StdResult<RentQuoteResponse> {
+    let property = query_property_info(deps, id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let rent = match property.expires {
+        Some(_) => rent_due(&property, &env.block, lease_period(&property, &config))?,
+        None => property.rent,
+    };
+    let (amount, rate) = rent_in_acudos(&deps.querier, &config, &property, rent)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(RentQuoteResponse {
+        rent,
+        currency: property.currency,
+        rate,
+        amount,
+    })
+}
+
+pub fn query_show_all_available_properties(deps: Deps) -> StdResult<Vec<u64>> {
+    Ok(vec![query_get_total_property(deps)?])
+}
+
+pub fn query_get_total_property(deps: Deps) -> StdResult<u64> {
+    Ok(PROPERTY_COUNT.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_reputation(deps: Deps, address: String) -> StdResult<ReputationResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let stats = REPUTATION
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(ReputationResponse {
+        address,
+        score: stats.score(),
+        stats,
+    })
+}
+
+pub fn query_permissions(
+    deps: Deps,
+    env: Env,
+    renter: String,
+    manager: String,
+) -> StdResult<PermissionsResponse> {
+    let renter = deps.api.addr_validate(&renter)?;
+    let manager = deps.api.addr_validate(&manager)?;
+    let active = |grant: &Grant| !grant.expires.is_expired(&env.block);
+
+    let all_properties = RENTER_MANAGERS
+        .may_load(deps.storage, (&renter, &manager))?
+        .filter(active);
+    let properties = PROPERTY_MANAGERS
+        .prefix((&renter, &manager))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (key, grant) = item?;
+            Ok((parse_id(&key)?, grant))
+        })
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, grant)| active(grant))
+        .collect();
+
+    Ok(PermissionsResponse {
+        all_properties,
+        properties,
+    })
+}
+
+pub fn query_deposit_stake(deps: Deps, env: Env, id: u64) -> StdResult<DepositStakeResponse> {
+    let property = query_property_info(deps, id)?;
+    let res = match deposit_stake(deps.storage, &deps.querier, &env, id)? {
+        Some((stake, amount, rewards)) => DepositStakeResponse {
+            validator: Some(stake.validator),
+            amount,
+            rewards,
+        },
+        None => DepositStakeResponse {
+            validator: None,
+            amount: property.escrow,
+            rewards: Uint128::zero(),
+        },
+    };
+    Ok(res)
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        lease_period: config.lease_period,
+        lease_period_seconds: config.lease_period_seconds,
+        max_batch_size: config.max_batch_size,
+        pull_rent: config.pull_rent,
+        landlord_group: config.landlord_group,
+        insurance: config.insurance,
+        oracle: config.oracle,
+        staking: config.staking,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    Ok(PauseStatusResponse {
+        all: pause.all,
+        actions: pause.actions,
+    })
+}
+
+pub fn query_claimable_payout(deps: Deps, address: String) -> StdResult<ClaimablePayoutResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let amount = CLAIMABLE_PAYOUTS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(ClaimablePayoutResponse { amount })
+}
+
+pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let balance = RENTER_BALANCES
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    Ok(BalanceResponse {
+        balance: balance.into_vec(),
+    })
+}
+
+pub fn query_tenant_overview(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TenantOverviewResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::from(id)));
+    let found = properties()
+        .idx
+        .rentee
+        .prefix(addr.as_bytes().to_vec())
+        .range(deps.storage, start, None, Order::Ascending);
+    // offers are keyed by property id and applicant, an applicant has one offer per property
+    let start = start_after.map(|id| Bound::exclusive((U64Key::from(id), &addr).joined_key()));
+    let offered = offers()
+        .idx
+        .applicant
+        .prefix(addr.as_bytes().to_vec())
+        .range(deps.storage, start, None, Order::Ascending);
+    let (leases, applications, offers) = overview(deps, &env, found, offered, limit)?;
+    let payments = PAYMENTS.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let offered = offers
+        .iter()
+        .fold(Uint128::zero(), |t, o| t + o.offer.escrow);
+    Ok(TenantOverviewResponse {
+        escrow: total_escrow(&leases, &applications) + offered,
+        next_due: next_due(&leases),
+        leases,
+        applications,
+        offers,
+        paid: payments.paid,
+    })
+}
+
+pub fn query_landlord_overview(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LandlordOverviewResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let listed = LISTINGS.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let start = start_after.map(|id| Bound::exclusive(U64Key::from(id)));
+    let found = properties()
+        .idx
+        .renter
+        .prefix(addr.as_bytes().to_vec())
+        .range(deps.storage, start, None, Order::Ascending);
+    let (leases, applications, _) = overview(deps, &env, found, std::iter::empty(), limit)?;
+    let payments = PAYMENTS.may_load(deps.storage, &addr)?.unwrap_or_default();
+    Ok(LandlordOverviewResponse {
+        properties: listed,
+        escrow: total_escrow(&leases, &applications),
+        next_due: next_due(&leases),
+        leases,
+        applications,
+        received: payments.received,
+    })
+}
+
+pub fn query_search_properties(
+    deps: Deps,
+    min_rent: Option<Uint128>,
+    max_rent: Option<Uint128>,
+    denom: Option<String>,
+    renter: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SearchPropertiesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let denom = denom.unwrap_or_else(|| String::from("acudos"));
+    // the empty currency holds the rented properties
+    if denom.is_empty() {
+        return Err(StdError::generic_err("denom must not be empty"));
+    }
+    let min_rent = min_rent.unwrap_or_default();
+    let index = properties().idx;
+
+    let found = match renter {
+        // the listings of one renter are fewer than a rent range spans, so they are filtered
+        Some(renter) => {
+            let renter = deps.api.addr_validate(&renter)?;
+            if is_suspended(deps.storage, renter.as_str()) {
+                return Ok(SearchPropertiesResponse { properties: vec![] });
+            }
+            let start = start_after.map(|id| Bound::exclusive(U64Key::from(id)));
+            let matches = |p: &FlatInfo| {
+                p.rentee.is_none()
+                    && p.currency.as_deref().unwrap_or("acudos") == denom
+                    && p.rent >= min_rent
+                    && max_rent.is_none_or(|max| p.rent <= max)
+            };
+            index
+                .renter
+                .prefix(renter.as_bytes().to_vec())
+                .range(deps.storage, start, None, Order::Ascending)
+                .filter(|item| item.as_ref().map_or(true, |(_, p)| matches(p)))
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        None => {
+            let mut min = Bound::inclusive_int(min_rent.u128());
+            if let Some(id) = start_after {
+                let last = query_property_info(deps, id)?;
+                if last.rent >= min_rent {
+                    let mut key = U128Key::from(last.rent.u128()).wrapped;
+                    key.extend(U64Key::from(id).wrapped);
+                    min = Bound::exclusive(key);
+                }
+            }
+            let max = max_rent
+                .and_then(|max| max.u128().checked_add(1))
+                .map(Bound::exclusive_int);
+            index
+                .rent
+                .prefix(denom.as_bytes().to_vec())
+                .range(deps.storage, Some(min), max, Order::Ascending)
+                .filter(|item| {
+                    item.as_ref()
+                        .map_or(true, |(_, p)| !is_suspended(deps.storage, &p.renter))
+                })
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?
+        }
+    };
+
+    let properties = found
+        .into_iter()
+        .map(|(key, property)| {
+            Ok(ListedProperty {
+                property_id: parse_id(&key)?,
+                renter: property.renter,
+                rent: property.rent,
+                currency: property.currency,
+                min_reputation: property.min_reputation,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(SearchPropertiesResponse { properties })
+}
+
+pub fn query_offers(
+    deps: Deps,
+    id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let offers = offers()
+        .prefix(U64Key::from(id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, offer) = item?;
+            let applicant = String::from_utf8(key)?;
+            Ok(ApplicantOffer { applicant, offer })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_bookings(
+    deps: Deps,
+    id: u64,
+    start_after: Option<Expiration>,
+    limit: Option<u32>,
+) -> StdResult<BookingsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(start) => Some(Bound::exclusive(U64Key::from(time_bound(&start)?.nanos()))),
+        None => None,
+    };
+    let bookings = BOOKINGS
+        .prefix(U64Key::from(id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<_>>()?;
+    Ok(BookingsResponse { bookings })
+}
+
+/// Returns the intervals without bookings between `from`, at the earliest now, and `until`.
+/// A page holds `limit` windows, the next one starts at the end of the last window.
+pub fn query_availability(
+    deps: Deps,
+    env: Env,
+    id: u64,
+    from: Option<Expiration>,
+    until: Expiration,
+    limit: Option<u32>,
+) -> StdResult<AvailabilityResponse> {
+    query_property_info(deps, id)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let from = match from {
+        Some(from) => time_bound(&from)?,
+        None => env.block.time,
+    };
+    let until = time_bound(&until)?;
+    let windows = free_windows(deps.storage, id, from.max(env.block.time), until, limit)?;
+    Ok(AvailabilityResponse { windows })
+}
+
+// Splits a page of the properties and offers found through the indexes into accepted leases,
+// pending requests and offers. Both are ordered by property id and merged, properties without
+// a rentee are left out. At most `limit` entries are returned.
+fn overview(
+    deps: Deps,
+    env: &Env,
+    found: impl Iterator<Item = StdResult<Pair<FlatInfo>>>,
+    offered: impl Iterator<Item = StdResult<Pair<Offer>>>,
+    limit: usize,
+) -> StdResult<(Vec<LeaseSummary>, Vec<Application>, Vec<PropertyOffer>)> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut found = found
+        .filter(|item| item.as_ref().map_or(true, |(_, p)| p.rentee.is_some()))
+        .peekable();
+    let mut offered = offered.peekable();
+    let mut leases = vec![];
+    let mut applications = vec![];
+    let mut offers = vec![];
+    for _ in 0..limit {
+        // a key of offers holds the length prefixed id in front of the applicant
+        let next_offer = match (found.peek(), offered.peek()) {
+            (None, None) => break,
+            (None, Some(_)) | (Some(Ok(_)), Some(Err(_))) => true,
+            (Some(Ok((key, _))), Some(Ok((offer_key, _)))) => {
+                offer_key.get(2..10) < Some(key.as_slice())
+            }
+            _ => false,
+        };
+        if next_offer {
+            if let Some(item) = offered.next() {
+                let (key, offer) = item?;
+                let (property_id, _) = parse_offer_key(&key)?;
+                offers.push(PropertyOffer { property_id, offer });
+            }
+            continue;
+        }
+        let (key, property) = match found.next() {
+            Some(item) => item?,
+            None => break,
+        };
+        let property_id = parse_id(&key)?;
+        let rentee = property.rentee.clone().unwrap_or_default();
+        match property.expires {
+            Some(expires) => leases.push(LeaseSummary {
+                property_id,
+                rent_due: rent_due(&property, &env.block, lease_period(&property, &config))?,
+                renter: property.renter,
+                rentee,
+                currency: property.currency,
+                expires,
+                ends: property.ends,
+                escrow: property.escrow,
+            }),
+            None => applications.push(Application {
+                property_id,
+                renter: property.renter,
+                rentee,
+                term: property.term,
+                escrow: property.escrow,
+            }),
+        }
+    }
+    Ok((leases, applications, offers))
+}
+
+fn total_escrow(leases: &[LeaseSummary], applications: &[Application]) -> Uint128 {
+    let leases = leases.iter().map(|l| l.escrow);
+    let applications = applications.iter().map(|a| a.escrow);
+    leases
+        .chain(applications)
+        .fold(Uint128::zero(), |t, e| t + e)
+}
+
+// earliest end of a paid period, when the next rent is due
+fn next_due(leases: &[LeaseSummary]) -> Option<Expiration> {
+    leases
+        .iter()
+        .map(|l| l.expires)
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+// splits a key of offers into the property id and the applicant
+fn parse_offer_key(key: &[u8]) -> StdResult<(u64, Addr)> {
+    // the id is prefixed with its length
+    let id = parse_id(key.get(2..).unwrap_or_default())?;
+    let applicant = String::from_utf8(key.get(10..).unwrap_or_default().to_vec())?;
+    Ok((id, Addr::unchecked(applicant)))
+}
+
+fn parse_id(data: &[u8]) -> StdResult<u64> {
+    match data.get(0..8).and_then(|bytes| bytes.try_into().ok()) {
+        Some(bytes) => Ok(u64::from_be_bytes(bytes)),
+        None => Err(StdError::parse_err(
+            "u64",
+            "Corrupted data found. 8 byte expected.",
+        )),
+    }
 }
@@ -1,4 +1,7 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError};
+
+use crate::state::LeaseAction;
+use cw_controllers::{AdminError, HookError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +9,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -30,7 +39,7 @@ pub enum ContractError {
     #[error("IsRented")]
     IsRented {},
 
-    #[error("Allowance is expired")]
+    #[error("Rent is overdue beyond the grace window, the lease is in default")]
     Expired {},
 
     #[error("Not Expired")]
@@ -44,4 +53,150 @@ pub enum ContractError {
 
     #[error("Rentee is already accepted")]
     IsAcceptedByRenter {},
+
+    #[error(
+        "Rent schedule must be non-empty, ascending and free of zero rents, increases at most 100%"
+    )]
+    InvalidRentSchedule {},
+
+    #[error("Lease term must be one to 1200 periods and end in the future")]
+    InvalidTerm {},
+
+    #[error("Lease term has ended")]
+    LeaseEnded {},
+
+    #[error("Rent for the whole lease term is already paid")]
+    TermFullyPaid {},
+
+    #[error("Lease does not have a fixed term")]
+    NoLeaseTerm {},
+
+    #[error("No renewal was offered by the renter")]
+    NoRenewalOffer {},
+
+    #[error("Renewal term differs from the one offered by the renter")]
+    RenewalMismatch {},
+
+    #[error("Reputation score {score} is below the required {required}")]
+    ReputationTooLow { score: i64, required: i64 },
+
+    #[error("Address is neither renter nor rentee of the lease")]
+    NotAParty {},
+
+    #[error("Lease period must be at least one block")]
+    InvalidLeasePeriod {},
+
+    #[error("{addr} is not a cw4 group contract")]
+    InvalidGroup { addr: String },
+
+    #[error("Only members of the landlord group can list properties")]
+    NotALandlord {},
+
+    #[error("Listing is suspended, its renter left the landlord group")]
+    PropertySuspended {},
+
+    #[error("Insurance premium must be above 0 and below 1")]
+    InvalidPremium {},
+
+    #[error("No price oracle is configured for rents in a reference currency")]
+    NoOracle {},
+
+    #[error("The reference currency of a rent must not be empty")]
+    InvalidCurrency {},
+
+    #[error("Oracle returned a zero rate")]
+    InvalidPrice {},
+
+    #[error("Oracle rate {rate} is above the accepted {max_rate}")]
+    SlippageExceeded { rate: Decimal, max_rate: Decimal },
+
+    #[error("Batch must not be empty")]
+    EmptyBatch {},
+
+    #[error("Batch of {size} units is above the limit of {max}")]
+    BatchTooLarge { size: u64, max: u32 },
+
+    #[error("Batch limit must be at least one unit")]
+    InvalidBatchSize {},
+
+    #[error("Grant needs at least one permission and must not be expired")]
+    InvalidGrant {},
+
+    #[error("No grant for this manager")]
+    NoGrant {},
+
+    #[error("Document hash must be a hex encoded sha256 hash")]
+    InvalidDocumentHash {},
+
+    #[error("Document hash differs from the one the rentee signed")]
+    DocumentMismatch {},
+
+    #[error("{action:?} is paused")]
+    Paused { action: LeaseAction },
+
+    #[error("Emergency withdraw needs the whole contract to be paused")]
+    NotPaused {},
+
+    #[error("Emergency withdraw is in progress, it has to be finished before unpausing")]
+    WithdrawalInProgress {},
+
+    #[error("No failed payout to claim")]
+    NothingToClaim {},
+
+    #[error("No accrued rent to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("Withdrawal exceeds the accrued rent")]
+    InsufficientBalance {},
+
+    #[error("Offers must be below the asking rent and expire in the future")]
+    InvalidOffer {},
+
+    #[error("An offer of the sender on this property is still open")]
+    OfferExists {},
+
+    #[error("No offer of the applicant on this property")]
+    NoOffer {},
+
+    #[error("The offer has expired")]
+    OfferExpired {},
+
+    #[error("Counter offers must be above the offered rent and at most the asking rent")]
+    InvalidCounter {},
+
+    #[error("The renter has not countered the offer")]
+    NoCounter {},
+
+    #[error("Validator {validator} is not known to the chain")]
+    UnknownValidator { validator: String },
+
+    #[error("Rentee share of the staking rewards must not be above 1")]
+    InvalidRewardShare {},
+
+    #[error("No unbonded deposit to claim")]
+    NothingUnbonded {},
+
+    #[error("Daily rate must be above 0")]
+    InvalidDailyRate {},
+
+    #[error("The property takes no bookings")]
+    BookingsClosed {},
+
+    #[error("Stays are booked in time, from the future until after their start")]
+    InvalidBooking {},
+
+    #[error("The stay overlaps another booking")]
+    BookingOverlap {},
+
+    #[error("No booking starting at this time")]
+    NoBooking {},
+
+    #[error("The stay has begun")]
+    BookingStarted {},
+
+    #[error("The stay is not over yet")]
+    BookingNotOver {},
+
+    #[error("The property is booked for an upcoming stay")]
+    PropertyBooked {},
 }
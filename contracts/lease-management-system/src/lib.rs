@@ -1,6 +1,9 @@
-mod test;
+pub mod calendar;
 pub mod contract;
-pub mod state;
-pub mod msg;
 mod error;
+pub mod msg;
+pub mod oracle;
+pub mod staking;
+pub mod state;
+mod test;
 pub use error::ContractError;
@@ -0,0 +1,30 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Decimal, QuerierWrapper, QueryRequest, StdResult, WasmQuery};
+
+/// Query interface the price oracle has to implement
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    /// Returns PriceResponse for the given currency
+    Price { currency: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PriceResponse {
+    /// acudos paid for one unit of the currency
+    pub rate: Decimal,
+}
+
+/// Reads the current rate of the currency from the oracle
+pub fn query_rate(querier: &QuerierWrapper, oracle: &Addr, currency: &str) -> StdResult<Decimal> {
+    let request = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: oracle.to_string(),
+        msg: to_binary(&OracleQueryMsg::Price {
+            currency: currency.to_string(),
+        })?,
+    });
+    let res: PriceResponse = querier.query(&request)?;
+    Ok(res.rate)
+}
@@ -0,0 +1,207 @@
+use cosmwasm_std::{
+    Addr, Coin, Decimal, DistributionMsg, Env, QuerierWrapper, StakingMsg, StdResult, Storage,
+    SubMsg, Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{
+    add_total, load_total, sub_total, DepositStaking, RewardPool, Stake, REWARDS_TOTAL,
+    REWARD_POOLS, STAKED_TOTAL, STAKES, UNBONDING, UNBONDING_TOTAL,
+};
+
+/// Deposit taken off a validator when its lease is closed
+pub struct Unstaked {
+    /// Delegated deposit, less what the validator was slashed
+    pub amount: Uint128,
+    pub rentee_rewards: Uint128,
+    pub renter_rewards: Uint128,
+    /// Reward withdrawal and undelegation, to be sent before the rewards are paid out
+    pub msgs: Vec<SubMsg>,
+}
+
+fn acudos(amount: Uint128) -> Coin {
+    Coin {
+        amount,
+        denom: String::from("acudos"),
+    }
+}
+
+/// acudos the contract has delegated to the validator and the rewards it has not withdrawn yet
+pub fn query_delegation(
+    querier: &QuerierWrapper,
+    contract: &Addr,
+    validator: &str,
+) -> StdResult<(Uint128, Uint128)> {
+    let delegation = match querier.query_delegation(contract, validator)? {
+        Some(delegation) => delegation,
+        None => return Ok((Uint128::zero(), Uint128::zero())),
+    };
+    let rewards = delegation
+        .accumulated_rewards
+        .iter()
+        .filter(|c| c.denom == "acudos")
+        .fold(Uint128::zero(), |total, c| total + c.amount);
+    Ok((delegation.amount.amount, rewards))
+}
+
+// Rewards the delegation query reports that are not yet in the index. The query keeps
+// reporting harvested rewards until the withdrawal runs after the message, and within a
+// block rewards only ever go down, so what was harvested in this block is left out.
+fn unharvested(pool: &RewardPool, height: u64, rewards: Uint128) -> Uint128 {
+    match pool.height == height {
+        true => rewards.saturating_sub(pool.harvested),
+        false => rewards,
+    }
+}
+
+// Loads the pool of the validator with its new rewards added to the reward index and to the
+// held rewards. Slashing happens between blocks, so the first harvest of a block also lowers
+// the bonded acudos to what is still delegated. Returns the pool for the caller to update and
+// save, and the withdrawal of the new rewards.
+fn harvest(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    validator: &str,
+) -> StdResult<(RewardPool, Option<SubMsg>)> {
+    let mut pool = REWARD_POOLS
+        .may_load(storage, validator)?
+        .unwrap_or_default();
+    let (delegated, rewards) = query_delegation(querier, &env.contract.address, validator)?;
+    let new = unharvested(&pool, env.block.height, rewards);
+    if pool.height != env.block.height {
+        pool.bonded = pool.bonded.min(delegated);
+        pool.height = env.block.height;
+        pool.harvested = Uint128::zero();
+    }
+    if new.is_zero() || pool.shares.is_zero() {
+        return Ok((pool, None));
+    }
+    pool.harvested += new;
+    pool.index = pool.index + Decimal::from_ratio(new, pool.shares);
+    add_total(storage, &REWARDS_TOTAL, new)?;
+    let withdraw = DistributionMsg::WithdrawDelegatorReward {
+        validator: validator.to_string(),
+    };
+    Ok((pool, Some(SubMsg::new(withdraw))))
+}
+
+/// Delegates the deposit of a lease just accepted, if deposits are staked
+pub fn stake_deposit(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    staking: Option<&DepositStaking>,
+    id: u64,
+    deposit: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let staking = match staking {
+        Some(staking) if !deposit.is_zero() => staking,
+        _ => return Ok(vec![]),
+    };
+    let (mut pool, withdraw) = harvest(storage, querier, env, &staking.validator)?;
+    let shares = match pool.shares.is_zero() || pool.bonded.is_zero() {
+        true => deposit,
+        false => deposit.multiply_ratio(pool.shares, pool.bonded),
+    };
+    let stake = Stake {
+        validator: staking.validator.clone(),
+        shares,
+        rentee_share: staking.rentee_share,
+        unbonding_period: staking.unbonding_period,
+        reward_index: pool.index,
+        deposit,
+    };
+    pool.shares += shares;
+    pool.bonded += deposit;
+    REWARD_POOLS.save(storage, &staking.validator, &pool)?;
+    STAKES.save(storage, U64Key::from(id), &stake)?;
+    add_total(storage, &STAKED_TOTAL, deposit)?;
+
+    let delegate = StakingMsg::Delegate {
+        validator: staking.validator.clone(),
+        amount: acudos(deposit),
+    };
+    Ok(withdraw
+        .into_iter()
+        .chain(Some(SubMsg::new(delegate)))
+        .collect())
+}
+
+/// Undelegates the deposit of a lease being closed, if it was staked. The deposit becomes a
+/// claim of the rentee released after the unbonding period, the rewards are split.
+pub fn unstake_deposit(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+    rentee: &str,
+) -> StdResult<Option<Unstaked>> {
+    let stake = match STAKES.may_load(storage, U64Key::from(id))? {
+        Some(stake) => stake,
+        None => return Ok(None),
+    };
+    STAKES.remove(storage, U64Key::from(id));
+    sub_total(storage, &STAKED_TOTAL, stake.deposit)?;
+    let (mut pool, withdraw) = harvest(storage, querier, env, &stake.validator)?;
+
+    let amount = pool.value(stake.shares);
+    let rewards = stake.shares * (pool.index - stake.reward_index);
+    let rentee_rewards = rewards * stake.rentee_share;
+    pool.shares = pool.shares.checked_sub(stake.shares)?;
+    pool.bonded = pool.bonded.checked_sub(amount)?;
+    REWARD_POOLS.save(storage, &stake.validator, &pool)?;
+    sub_total(storage, &REWARDS_TOTAL, rewards)?;
+
+    let mut msgs: Vec<SubMsg> = withdraw.into_iter().collect();
+    if !amount.is_zero() {
+        let undelegate = StakingMsg::Undelegate {
+            validator: stake.validator,
+            amount: acudos(amount),
+        };
+        msgs.push(SubMsg::new(undelegate));
+        let release_at = stake.unbonding_period.after(&env.block);
+        UNBONDING.create_claim(storage, &Addr::unchecked(rentee), amount, release_at)?;
+        add_total(storage, &UNBONDING_TOTAL, amount)?;
+    }
+    Ok(Some(Unstaked {
+        amount,
+        rentee_rewards,
+        renter_rewards: rewards - rentee_rewards,
+        msgs,
+    }))
+}
+
+/// Delegated acudos and rewards so far of the deposit held for the property
+pub fn deposit_stake(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    id: u64,
+) -> StdResult<Option<(Stake, Uint128, Uint128)>> {
+    let stake = match STAKES.may_load(storage, U64Key::from(id))? {
+        Some(stake) => stake,
+        None => return Ok(None),
+    };
+    let mut pool = REWARD_POOLS.load(storage, &stake.validator)?;
+    let (delegated, rewards) = query_delegation(querier, &env.contract.address, &stake.validator)?;
+    let new = unharvested(&pool, env.block.height, rewards);
+    if pool.height != env.block.height {
+        pool.bonded = pool.bonded.min(delegated);
+    }
+    let amount = pool.value(stake.shares);
+    let rewards = stake.shares * (pool.index - stake.reward_index)
+        + new.multiply_ratio(stake.shares, pool.shares);
+    Ok(Some((stake, amount, rewards)))
+}
+
+/// Harvested rewards of the running stakes, they are held by the contract until paid out
+pub fn held_rewards(storage: &dyn Storage) -> StdResult<Uint128> {
+    load_total(storage, &REWARDS_TOTAL)
+}
+
+/// Undelegated deposits the rentees have not claimed yet. Those still unbonding are counted
+/// as well, they are owed to the rentees once they arrive.
+pub fn unbonding_total(storage: &dyn Storage) -> StdResult<Uint128> {
+    load_total(storage, &UNBONDING_TOTAL)
+}
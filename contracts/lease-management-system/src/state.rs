@@ -1,22 +1,262 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw0::Expiration;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw0::{Duration, NativeBalance};
+use cw_controllers::{Admin, Claims, Hooks};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, IndexedSnapshotMap, Item, Map, MultiIndex, Strategy, U128Key,
+    U64Key,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub use lease::{
+    Booking, Config, DepositStaking, FlatInfo, Grant, Insurance, LeaseAction, LeaseDocument,
+    LeaseTerm, Offer, PauseState, Permission, RentSchedule, Reputation,
+};
+
+/// Transfer to a renter or rentee waiting for its outcome, the reply reads it if it fails
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
-pub struct FlatInfo {
-    pub renter: String,
-    pub rentee: Option<String>,
-    pub rent: Uint128,
-    pub expires: Option<Expiration>,
+pub struct PendingPayout {
+    pub recipient: Addr,
+    pub amount: Uint128,
+    /// Block height the payout was sent at
+    pub height: u64,
+}
+
+/// acudos of rent an address paid as rentee and received as renter over all its leases
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Default)]
+pub struct LifetimePayments {
+    pub paid: Uint128,
+    pub received: Uint128,
 }
 
+/// Deposit of an accepted lease delegated to a validator
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Stake {
+    pub validator: String,
+    /// Share of the acudos bonded to the validator, slashing shrinks all shares alike
+    pub shares: Uint128,
+    /// Staking settings at acceptance, changing them later does not touch running leases
+    pub rentee_share: Decimal,
+    pub unbonding_period: Duration,
+    /// Reward index of the validator when the deposit was delegated
+    pub reward_index: Decimal,
+    /// acudos delegated at acceptance, the escrow of the lease
+    #[serde(default)]
+    pub deposit: Uint128,
+}
+
+/// Deposits delegated to a validator and their rewards, shared pro rata by shares
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Default)]
+pub struct RewardPool {
+    pub shares: Uint128,
+    /// acudos bonded for the shares, lowered when the validator was slashed
+    pub bonded: Uint128,
+    /// acudos of rewards earned per share since the first delegation
+    pub index: Decimal,
+    /// Block height of the last harvest
+    pub height: u64,
+    /// Rewards counted at `height`. The delegation query keeps returning them until the
+    /// withdrawal runs, so harvesting again in the same block only counts what is above.
+    pub harvested: Uint128,
+}
+
+impl RewardPool {
+    /// acudos bonded for the given shares
+    pub fn value(&self, shares: Uint128) -> Uint128 {
+        match self.shares.is_zero() {
+            true => Uint128::zero(),
+            false => shares.multiply_ratio(self.bonded, self.shares),
+        }
+    }
+}
 
-pub const FLAT_LIST: Item<Vec<FlatInfo>>= Item::new("flatlist");
+/// Escrows an emergency withdrawal goes through, in this order
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowKind {
+    Property,
+    Offer,
+    Booking,
+}
+
+/// Emergency withdrawal spread over several calls. The escrows are refunded pro rata to
+/// the funds held when it started.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Withdrawal {
+    /// acudos escrowed over all properties, offers and bookings when it started
+    pub total: Uint128,
+    /// acudos available for the escrows when it started, at most `total`
+    pub available: Uint128,
+    /// Escrows handled by the next call
+    pub kind: EscrowKind,
+    /// Raw key of the last escrow of `kind` handled
+    pub cursor: Option<Vec<u8>>,
+}
+
+pub struct PropertyIndexes<'a> {
+    pub renter: MultiIndex<'a, (Vec<u8>, Vec<u8>), FlatInfo>,
+    /// Rentee of an accepted lease or of a pending request, unrented properties are
+    /// indexed under an empty rentee
+    pub rentee: MultiIndex<'a, (Vec<u8>, Vec<u8>), FlatInfo>,
+    /// Properties open for requests by currency (acudos if not set), then by rent and id.
+    /// The rent is stored big endian in front of the id, so ranges within a currency can
+    /// be scanned with integer bounds. Rented ones are under an empty currency, which no
+    /// listing or search can use.
+    pub rent: MultiIndex<'a, (Vec<u8>, Vec<u8>), FlatInfo>,
+}
+
+impl<'a> IndexList<FlatInfo> for PropertyIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FlatInfo>> + '_> {
+        let v: Vec<&dyn Index<FlatInfo>> = vec![&self.renter, &self.rentee, &self.rent];
+        Box::new(v.into_iter())
+    }
+}
+
+fn rent_index(property: &FlatInfo, pk: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+    let currency = match property.rentee.is_some() {
+        true => "",
+        false => property.currency.as_deref().unwrap_or("acudos"),
+    };
+    let mut rent = U128Key::from(property.rent.u128()).wrapped;
+    rent.extend(pk);
+    (currency.as_bytes().to_vec(), rent)
+}
+
+/// Every property keyed by its id, indexed by renter and rentee. Each block keeps a
+/// changelog, so the state of a property (who occupied it and at which rent) can be
+/// read back at any past height.
+pub fn properties<'a>() -> IndexedSnapshotMap<'a, U64Key, FlatInfo, PropertyIndexes<'a>> {
+    let indexes = PropertyIndexes {
+        renter: MultiIndex::new(
+            |p, pk| (p.renter.as_bytes().to_vec(), pk),
+            "properties",
+            "properties__renter",
+        ),
+        rentee: MultiIndex::new(
+            |p, pk| {
+                (
+                    p.rentee.as_deref().unwrap_or_default().as_bytes().to_vec(),
+                    pk,
+                )
+            },
+            "properties",
+            "properties__rentee",
+        ),
+        rent: MultiIndex::new(rent_index, "properties", "properties__rent"),
+    };
+    IndexedSnapshotMap::new(
+        "properties",
+        "properties__checkpoints",
+        "properties__changelog",
+        Strategy::EveryBlock,
+        indexes,
+    )
+}
+
+pub struct OfferIndexes<'a> {
+    pub applicant: MultiIndex<'a, (Vec<u8>, Vec<u8>), Offer>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.applicant];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Open offers keyed by (property id, applicant), indexed by applicant
+pub fn offers<'a>() -> IndexedMap<'a, (U64Key, &'a Addr), Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        // the key holds the length prefixed id, then the applicant
+        applicant: MultiIndex::new(
+            |_, pk| (pk.get(10..).unwrap_or_default().to_vec(), pk),
+            "offers",
+            "offers__applicant",
+        ),
+    };
+    IndexedMap::new("offers", indexes)
+}
+
+/// Number of properties ever listed, the next property gets this value as id.
+/// It only ever grows, so ids are never reused.
+pub const PROPERTY_COUNT: Item<u64> = Item::new("property_count");
+/// Number of properties listed by each renter
+pub const LISTINGS: Map<&Addr, u64> = Map::new("listings");
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PAUSE: Item<PauseState> = Item::new("pause");
+/// Id of the property the next ProcessExpired sweep starts at
+pub const SWEEP_CURSOR: Item<u64> = Item::new("sweep_cursor");
+/// Emergency withdrawal not finished yet
+pub const WITHDRAWAL: Item<Withdrawal> = Item::new("withdrawal");
+/// acudos escrowed for all requests, leases, offers and bookings, staked deposits included
+pub const ESCROW_TOTAL: Item<Uint128> = Item::new("escrow_total");
+/// Number of payouts ever sent, the next payout gets this value as reply id
+pub const PAYOUT_COUNT: Item<u64> = Item::new("payout_count");
+/// Payouts sent as submessages, keyed by reply id. Successful ones are pruned in later blocks.
+pub const PENDING_PAYOUTS: Map<U64Key, PendingPayout> = Map::new("pending_payouts");
+/// acudos of failed payouts, the recipient pulls them with ClaimPayout
+pub const CLAIMABLE_PAYOUTS: Map<&Addr, Uint128> = Map::new("claimable_payouts");
+/// acudos claimable by all recipients of failed payouts
+pub const CLAIMABLE_TOTAL: Item<Uint128> = Item::new("claimable_total");
+/// Rent accrued for renters while `pull_rent` is set, they pull it with Withdraw
+pub const RENTER_BALANCES: Map<&Addr, NativeBalance> = Map::new("renter_balances");
+/// acudos accrued in the balances of all renters
+pub const ACCRUED_TOTAL: Item<Uint128> = Item::new("accrued_total");
+/// Calendar of every property, bookings keyed by (property id, start in nanoseconds)
+pub const BOOKINGS: Map<(U64Key, U64Key), Booking> = Map::new("bookings");
+/// Delegated deposits keyed by property id
+pub const STAKES: Map<U64Key, Stake> = Map::new("stakes");
+pub const REWARD_POOLS: Map<&str, RewardPool> = Map::new("reward_pools");
+/// acudos of the deposits delegated for running leases
+pub const STAKED_TOTAL: Item<Uint128> = Item::new("staked_total");
+/// Harvested rewards of all validators not paid out yet
+pub const REWARDS_TOTAL: Item<Uint128> = Item::new("rewards_total");
+/// Undelegated deposits the rentees claim with ClaimUnbonded once unbonded
+pub const UNBONDING: Claims = Claims::new("unbonding");
+/// acudos of the undelegated deposits not claimed yet, whether still unbonding or not
+pub const UNBONDING_TOTAL: Item<Uint128> = Item::new("unbonding_total");
 pub const OWNER: Item<Addr> = Item::new("owner");
+/// Manages the hooks, set to the instantiating address
+pub const ADMIN: Admin = Admin::new("admin");
+/// Contracts notified about every lease lifecycle transition
+pub const HOOKS: Hooks = Hooks::new("lease-hooks");
 pub const DENOM: Item<String> = Item::new("denom");
-pub const RENTER_TO_FLAT_ID: Map<&Addr,Vec<usize>>= Map::new("renter_to_flatid");
+pub const REPUTATION: Map<&Addr, Reputation> = Map::new("reputation");
+/// Renters that left the landlord group with the height they left at. No new lease can be
+/// requested on their listings until they are members again.
+pub const SUSPENDED_LANDLORDS: Map<&Addr, u64> = Map::new("suspended_landlords");
+pub const PAYMENTS: Map<&Addr, LifetimePayments> = Map::new("payments");
+/// Grants of a renter to a manager covering all properties of the renter, keyed by (renter, manager)
+pub const RENTER_MANAGERS: Map<(&Addr, &Addr), Grant> = Map::new("renter_managers");
+/// Grants of a renter to a manager for a single property, keyed by (renter, manager, property id)
+pub const PROPERTY_MANAGERS: Map<(&Addr, &Addr, U64Key), Grant> = Map::new("property_managers");
+
+/// Current value of one of the running totals of acudos
+pub fn load_total(store: &dyn Storage, total: &Item<Uint128>) -> StdResult<Uint128> {
+    Ok(total.may_load(store)?.unwrap_or_default())
+}
 
+/// Adds to one of the running totals of acudos
+pub fn add_total(store: &mut dyn Storage, total: &Item<Uint128>, amount: Uint128) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let value = load_total(store, total)?.checked_add(amount)?;
+    total.save(store, &value)
+}
 
+/// Takes from one of the running totals of acudos
+pub fn sub_total(store: &mut dyn Storage, total: &Item<Uint128>, amount: Uint128) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let value = load_total(store, total)?.saturating_sub(amount);
+    total.save(store, &value)
+}
 
+/// Hands out the id for a newly listed property and bumps the counter
+pub fn next_property_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id = PROPERTY_COUNT.may_load(store)?.unwrap_or_default();
+    PROPERTY_COUNT.save(store, &(id + 1))?;
+    Ok(id)
+}
@@ -1,27 +1,53 @@
 #![cfg(test)]
 use crate::{
     contract::{
-        execute, instantiate, query_get_total_property, query_property_info,
+        execute, instantiate, query, query_get_total_property, query_lease_status,
+        query_permissions, query_property_at_height, query_property_info, query_reputation,
         query_show_all_available_properties,
     },
-    msg::{ExecuteMsg, InstantiateMsg},
-    state::FlatInfo,
+    msg::{
+        BalanceResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, InsuranceInfo,
+        LandlordOverviewResponse, LeaseStatusResponse, NewProperty, PauseStatusResponse,
+        PropertyResponse, QueryMsg, RentOffer, RentUpdate, ReputationResponse,
+        SearchPropertiesResponse, TenantOverviewResponse,
+    },
+    state::{
+        load_total, offers, properties, FlatInfo, LeaseAction, LeaseDocument, LeaseTerm,
+        Permission, RentSchedule, Reputation, BOOKINGS, ESCROW_TOTAL,
+    },
     ContractError,
 };
 use cosmwasm_std::{
-    coins,
+    coins, from_binary, from_slice,
     testing::{mock_dependencies, mock_env, mock_info},
-    StdError, Uint128,
+    BankMsg, Decimal, Order, StdError, SubMsg, Uint128,
 };
-use cosmwasm_std::{Coin, DepsMut};
-use cw0::Expiration;
+use cosmwasm_std::{Coin, Deps, DepsMut};
+use cw0::{Duration, Expiration};
+use cw4::MemberChangedHookMsg;
+use cw_controllers::{AdminError, HookError, HooksResponse};
+use lease::{LeaseChangedHookMsg, LeaseEvent};
+use proptest::prelude::*;
 
-fn do_instantiate(deps: DepsMut, owner: &String) {
-    let msg = InstantiateMsg {};
+fn do_instantiate(deps: DepsMut, owner: &str) {
+    let msg = InstantiateMsg {
+        landlord_group: None,
+    };
     let info = mock_info(owner, &[]);
     instantiate(deps, mock_env(), info, msg).unwrap();
 }
 
+// transfer to a renter or rentee, replying if it fails
+fn payout(id: u64, to_address: &str, amount: u128) -> SubMsg {
+    SubMsg::reply_on_error(
+        BankMsg::Send {
+            to_address: to_address.to_string(),
+            amount: coins(amount, "acudos"),
+        },
+        id,
+    )
+}
+
 #[test]
 fn add_property() {
     let mut deps = mock_dependencies(&[]);
@@ -32,17 +58,32 @@ fn add_property() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     println!("working");
-    let d = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let d = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         d,
         FlatInfo {
             renter: renter.to_string(),
             rent: Uint128::new(200),
             rentee: None,
-            expires: None
+            expires: None,
+            rent_schedule: None,
+            accepted_at: None,
+            accepted_time: None,
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::zero(),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: None,
+            agreed_rent: None,
         }
     );
 
@@ -51,16 +92,31 @@ fn add_property() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(300),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let d = query_property_info(deps.as_ref(), usize::from(1u16)).unwrap();
+    let d = query_property_info(deps.as_ref(), 1).unwrap();
     assert_eq!(
         d,
         FlatInfo {
             renter: renter.to_string(),
             rent: Uint128::new(300),
             rentee: None,
-            expires: None
+            expires: None,
+            rent_schedule: None,
+            accepted_at: None,
+            accepted_time: None,
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::zero(),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: None,
+            agreed_rent: None,
         }
     );
 }
@@ -73,12 +129,30 @@ fn request_lease() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
+    // renters with listings cannot rent
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(renter.as_str(), &coins(400u128, "acudos"));
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRentee {});
+
     // appropriate denom is not given
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -93,7 +167,11 @@ fn request_lease() {
 
     // id not present
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(2u16),
+        property_id: 2,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -108,7 +186,11 @@ fn request_lease() {
 
     // Less than Rent error
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -123,7 +205,11 @@ fn request_lease() {
 
     // Success response
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -137,7 +223,11 @@ fn request_lease() {
 
     // once requested cannot request again
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("new-rentee");
     let info = mock_info(
@@ -160,12 +250,16 @@ fn accept_lease() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     // if rentee is not present
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
@@ -174,33 +268,54 @@ fn accept_lease() {
 
     // request for lease
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(600u128, String::from("acudos")));
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    let q = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         q,
         FlatInfo {
             expires: Some(Expiration::AtHeight(env.block.height + 411428)),
             rent: Uint128::new(200),
             rentee: Some(rentee.to_string()),
-            renter: renter.to_string()
+            renter: renter.to_string(),
+            rent_schedule: None,
+            accepted_at: Some(env.block.height),
+            accepted_time: Some(env.block.time),
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: Some(Duration::Height(411428)),
+            agreed_rent: None,
         }
     );
 
     // invalid renter
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("new-renter");
     let info = mock_info(renter.as_str(), &[]);
@@ -217,11 +332,17 @@ fn terminate_lease() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
 
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -234,9 +355,7 @@ fn terminate_lease() {
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     // error if rentee is not accepted by renter then renter cannot terminate the lease.
-    let msg = ExecuteMsg::TerminateLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
@@ -244,7 +363,9 @@ fn terminate_lease() {
     assert_eq!(err, ContractError::IsNotRented {});
 
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
@@ -252,9 +373,7 @@ fn terminate_lease() {
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     // renter cannot terminate the lease if agreement is not expired
-    let msg = ExecuteMsg::TerminateLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let mut env1 = mock_env();
@@ -262,14 +381,20 @@ fn terminate_lease() {
     let err = execute(deps.as_mut(), env1, info, msg).unwrap_err();
     assert_eq!(err, ContractError::NotExpired {});
 
+    // nor while the rentee may still pay late within the grace window
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
+    let info = mock_info(renter.as_str(), &[]);
+    let mut env1 = mock_env();
+    env1.block.height = env.block.height + 411428u64 + 41141u64;
+    let err = execute(deps.as_mut(), env1, info, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotExpired {});
+
     // Success terminate, only possible if rentee lease is expired.
-    let msg = ExecuteMsg::TerminateLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let mut env1 = mock_env();
-    env1.block.height = env.block.height + 411430u64;
+    env1.block.height = env.block.height + 411428u64 + 41142u64;
     execute(deps.as_mut(), env1, info, msg).unwrap();
 }
 
@@ -282,11 +407,17 @@ fn pay_rent() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(
@@ -300,7 +431,8 @@ fn pay_rent() {
 
     // error if rentee trying to pay the rent without approval from renter
     let msg = ExecuteMsg::PayRent {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        max_rate: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
@@ -309,7 +441,9 @@ fn pay_rent() {
     assert_eq!(err, ContractError::ExpirationDoesNotExist {});
 
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
@@ -318,13 +452,14 @@ fn pay_rent() {
 
     // pay rent 1 time within the month end extends the expiry upto second month
     let msg = ExecuteMsg::PayRent {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        max_rate: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    let q = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         q,
         FlatInfo {
@@ -333,19 +468,33 @@ fn pay_rent() {
             rent: Uint128::new(200),
             expires: Some(Expiration::AtHeight(
                 env.clone().block.height + 411428u64 * 2
-            ))
+            )),
+            rent_schedule: None,
+            accepted_at: Some(env.block.height),
+            accepted_time: Some(env.block.time),
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: Some(Duration::Height(411428)),
+            agreed_rent: None,
         }
     );
 
     // pay rent 2 time within the month end extends the expiry upto third month
     let msg = ExecuteMsg::PayRent {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        max_rate: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    let q = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         q,
         FlatInfo {
@@ -354,16 +503,35 @@ fn pay_rent() {
             rent: Uint128::new(200),
             expires: Some(Expiration::AtHeight(
                 env.clone().block.height + 411428u64 * 3
-            ))
+            )),
+            rent_schedule: None,
+            accepted_at: Some(env.block.height),
+            accepted_time: Some(env.block.time),
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: Some(Duration::Height(411428)),
+            agreed_rent: None,
         }
     );
     // error if less than requested rent is paid by the rentee.
     let msg = ExecuteMsg::PayRent {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        max_rate: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(100u128, "acudos"));
     let env = mock_env();
+    let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::LessThanRent {}));
+
+    // so is paying without any acudos
+    let info = mock_info(rentee.as_str(), &[]);
     let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
     assert!(matches!(err, ContractError::LessThanRent {}));
 }
@@ -376,12 +544,12 @@ fn reject_lease() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     // error if no rentee is requested for lease
-    let msg = ExecuteMsg::RejectLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::RejectLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
@@ -390,16 +558,18 @@ fn reject_lease() {
 
     // error if invalid renter is trying to reject lease
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    let msg = ExecuteMsg::RejectLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::RejectLease { property_id: 0 };
     let renter = String::from("new-renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
@@ -407,36 +577,49 @@ fn reject_lease() {
     assert_eq!(err, ContractError::InvalidRenter {});
 
     // successful rejection of request for lease by renter
-    let msg = ExecuteMsg::RejectLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::RejectLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    let q = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         q,
         FlatInfo {
             renter: renter.to_string(),
             rentee: None,
             rent: Uint128::new(200),
-            expires: None
+            expires: None,
+            rent_schedule: None,
+            accepted_at: None,
+            accepted_time: None,
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::zero(),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: None,
+            agreed_rent: None,
         }
     );
 
     // error if invalid renter trying to reject the rentee
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    let msg = ExecuteMsg::RejectLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::RejectLease { property_id: 0 };
     let renter = String::from("new-renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
@@ -445,16 +628,16 @@ fn reject_lease() {
 
     // error if rentee accepted by the renter then renter cannot reject it later
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    let msg = ExecuteMsg::RejectLease {
-        property_id: usize::from(0u16),
-    };
+    let msg = ExecuteMsg::RejectLease { property_id: 0 };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
     let env = mock_env();
@@ -470,6 +653,8 @@ fn query_get_total_properties() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -477,11 +662,13 @@ fn query_get_total_properties() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(300),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let total = query_get_total_property(deps.as_ref()).unwrap();
-    assert_eq!(total, usize::from(2u16));
+    assert_eq!(total, 2);
 }
 #[test]
 fn query_show_all_available() {
@@ -492,6 +679,8 @@ fn query_show_all_available() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -499,18 +688,26 @@ fn query_show_all_available() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(300),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::RequestForLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
     };
     let rentee = String::from("rentee");
     let info = mock_info(rentee.as_str(), &coins(600u128, String::from("acudos")));
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let msg = ExecuteMsg::AcceptLease {
-        property_id: usize::from(0u16),
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
     };
     let renter = String::from("renter");
     let info = mock_info(renter.as_str(), &[]);
@@ -518,7 +715,7 @@ fn query_show_all_available() {
     execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     let q = query_show_all_available_properties(deps.as_ref()).unwrap();
-    assert_eq!(q, vec![usize::from(2u16)]);
+    assert_eq!(q, vec![2]);
 }
 
 #[test]
@@ -530,6 +727,8 @@ fn query_property() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -537,19 +736,34 @@ fn query_property() {
     let info = mock_info(renter.as_str(), &[]);
     let msg = ExecuteMsg::AddProperty {
         rent: Uint128::new(300),
+        min_reputation: None,
+        currency: None,
     };
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-    let q = query_property_info(deps.as_ref(), usize::from(0u16)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
     assert_eq!(
         q,
         FlatInfo {
             renter: renter.to_string(),
             rentee: None,
             rent: Uint128::new(200),
-            expires: None
+            expires: None,
+            rent_schedule: None,
+            accepted_at: None,
+            accepted_time: None,
+            term: None,
+            ends: None,
+            renewal: None,
+            escrow: Uint128::zero(),
+            min_reputation: None,
+            currency: None,
+            document: None,
+            daily_rate: None,
+            lease_period: None,
+            agreed_rent: None,
         }
     );
-    let q = query_property_info(deps.as_ref(), usize::from(3u16)).unwrap_err();
+    let q = query_property_info(deps.as_ref(), 3).unwrap_err();
     assert_eq!(
         q,
         StdError::NotFound {
@@ -557,3 +771,2291 @@ fn query_property() {
         }
     );
 }
+
+#[test]
+fn query_property_history() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+
+    // property listed at the mock height
+    let listed = mock_env();
+    let renter = String::from("renter");
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), listed.clone(), info, msg).unwrap();
+
+    // requested 10 blocks later
+    let mut requested = mock_env();
+    requested.block.height = listed.block.height + 10;
+    let rentee = String::from("rentee");
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    execute(deps.as_mut(), requested.clone(), info, msg).unwrap();
+
+    // accepted 10 blocks after that
+    let mut accepted = mock_env();
+    accepted.block.height = requested.block.height + 10;
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    execute(deps.as_mut(), accepted.clone(), info, msg).unwrap();
+
+    let vacant = FlatInfo {
+        renter: renter.to_string(),
+        rentee: None,
+        rent: Uint128::new(200),
+        expires: None,
+        rent_schedule: None,
+        accepted_at: None,
+        accepted_time: None,
+        term: None,
+        ends: None,
+        renewal: None,
+        escrow: Uint128::zero(),
+        min_reputation: None,
+        currency: None,
+        document: None,
+        daily_rate: None,
+        lease_period: None,
+        agreed_rent: None,
+    };
+    let requested_state = FlatInfo {
+        rentee: Some(rentee.to_string()),
+        escrow: Uint128::new(400),
+        ..vacant.clone()
+    };
+    let leased = FlatInfo {
+        expires: Some(Expiration::AtHeight(accepted.block.height + 411428)),
+        accepted_at: Some(accepted.block.height),
+        accepted_time: Some(accepted.block.time),
+        lease_period: Some(Duration::Height(411428)),
+        agreed_rent: None,
+        escrow: Uint128::new(200),
+        ..requested_state.clone()
+    };
+
+    // not listed yet at the beginning of the listing block
+    let q = query_property_at_height(deps.as_ref(), 0, listed.block.height).unwrap();
+    assert_eq!(q, None);
+    let q = query_property_at_height(deps.as_ref(), 0, listed.block.height + 1).unwrap();
+    assert_eq!(q, Some(vacant.clone()));
+    let q = query_property_at_height(deps.as_ref(), 0, requested.block.height).unwrap();
+    assert_eq!(q, Some(vacant));
+    let q = query_property_at_height(deps.as_ref(), 0, requested.block.height + 1).unwrap();
+    assert_eq!(q, Some(requested_state.clone()));
+    let q = query_property_at_height(deps.as_ref(), 0, accepted.block.height).unwrap();
+    assert_eq!(q, Some(requested_state));
+    let q = query_property_at_height(deps.as_ref(), 0, accepted.block.height + 1).unwrap();
+    assert_eq!(q, Some(leased.clone()));
+    // heights after the last change show the current state
+    let q = query_property_at_height(deps.as_ref(), 0, accepted.block.height + 1000).unwrap();
+    assert_eq!(q, Some(leased));
+
+    // unknown property
+    let err = query_property_at_height(deps.as_ref(), 1, accepted.block.height).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::NotFound {
+            kind: String::from("property not found"),
+        }
+    );
+}
+
+#[test]
+fn pay_rent_with_schedule() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    for _ in 0..2 {
+        let info = mock_info(renter.as_str(), &[]);
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    for id in 0..2 {
+        let msg = ExecuteMsg::RequestForLease {
+            property_id: id,
+            term: None,
+            max_rate: None,
+            document: None,
+            offer: None,
+        };
+        let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let env = mock_env();
+
+    // steps must be ascending
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: Some(RentSchedule::Steps(vec![
+            (
+                Expiration::AtHeight(env.block.height + 200),
+                Uint128::new(250),
+            ),
+            (
+                Expiration::AtHeight(env.block.height + 100),
+                Uint128::new(220),
+            ),
+        ])),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRentSchedule {});
+
+    // fixed steps: 220 after 100 blocks, 250 after 200 blocks
+    let schedule = RentSchedule::Steps(vec![
+        (
+            Expiration::AtHeight(env.block.height + 100),
+            Uint128::new(220),
+        ),
+        (
+            Expiration::AtHeight(env.block.height + 200),
+            Uint128::new(250),
+        ),
+    ]);
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: Some(schedule.clone()),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let status = query_lease_status(deps.as_ref(), env.clone(), 0).unwrap();
+    assert_eq!(
+        status,
+        LeaseStatusResponse {
+            rentee: Some(rentee.clone()),
+            expires: Some(Expiration::AtHeight(env.block.height + 411428)),
+            rent_due: Some(Uint128::new(200)),
+            rent_schedule: Some(schedule),
+            grace_ends: Some(Expiration::AtHeight(env.block.height + 411428 + 41142)),
+            ends: None,
+            ended: false,
+            renewal: None,
+            document: None,
+        }
+    );
+
+    // once the first step is reached its rent is due, the excess is refunded
+    let mut later = mock_env();
+    later.block.height = env.block.height + 150;
+    let status = query_lease_status(deps.as_ref(), later.clone(), 0).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(220)));
+
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    let err = execute(deps.as_mut(), later.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::LessThanRent {});
+
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(230u128, "acudos"));
+    let res = execute(deps.as_mut(), later, info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            payout(1, &renter, 220),
+            SubMsg::new(BankMsg::Send {
+                to_address: rentee.clone(),
+                amount: coins(10, "acudos"),
+            }),
+        ]
+    );
+
+    // +10% every second period
+    let schedule = RentSchedule::Percentage {
+        increase: Decimal::percent(10),
+        every_periods: 2,
+    };
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: Some(schedule),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let mut later = mock_env();
+    later.block.height = env.block.height + 411428;
+    let status = query_lease_status(deps.as_ref(), later.clone(), 1).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(200)));
+    later.block.height = env.block.height + 411428 * 2;
+    let status = query_lease_status(deps.as_ref(), later.clone(), 1).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(220)));
+    later.block.height = env.block.height + 411428 * 4;
+    let status = query_lease_status(deps.as_ref(), later.clone(), 1).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(242)));
+
+    // after two hundred increases the rent is computed without stepping through each of them
+    later.block.height = env.block.height + 411428 * 400;
+    let status = query_lease_status(deps.as_ref(), later, 1).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(37_981_055_292)));
+}
+
+#[test]
+fn rent_schedule_overflow() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap();
+
+    // an increase can at most double the rent
+    let env = mock_env();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: Some(RentSchedule::Percentage {
+            increase: Decimal::percent(101),
+            every_periods: 1,
+        }),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRentSchedule {});
+
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: Some(RentSchedule::Percentage {
+            increase: Decimal::one(),
+            every_periods: 1,
+        }),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // once the rent no longer fits, quoting fails instead of panicking
+    let mut later = mock_env();
+    later.block.height = env.block.height + 411428 * 130;
+    let err = query_lease_status(deps.as_ref(), later.clone(), 0).unwrap_err();
+    assert!(matches!(err, StdError::Overflow { .. }));
+    let msg = QueryMsg::RentQuote { property_id: 0 };
+    let err = query(deps.as_ref(), later.clone(), msg).unwrap_err();
+    assert!(matches!(err, StdError::Overflow { .. }));
+
+    // long past the paid period the rentee is in default, rent is not taken anymore
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    let err = execute(deps.as_mut(), later, info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Expired {});
+}
+
+#[test]
+fn fixed_term_lease() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    for _ in 0..2 {
+        let info = mock_info(renter.as_str(), &[]);
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let env = mock_env();
+    let period = 411428u64;
+
+    // a term must cover at least one period
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: Some(LeaseTerm::Periods(0)),
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTerm {});
+
+    // request a lease for two periods, the excess is refunded
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: Some(LeaseTerm::Periods(2)),
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(500u128, "acudos"));
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: rentee.clone(),
+            amount: coins(100, "acudos"),
+        })]
+    );
+
+    // accepting releases the first rent to the renter
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(0, &renter, 200)]);
+    let ends = Expiration::AtHeight(env.block.height + period * 2);
+    let status = query_lease_status(deps.as_ref(), env.clone(), 0).unwrap();
+    assert_eq!(status.ends, Some(ends));
+    assert!(!status.ended);
+
+    // rent can only be paid up to the end of the term
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::TermFullyPaid {});
+
+    // the deposit cannot be claimed before the end
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 0 };
+    let info = mock_info(rentee.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotExpired {});
+
+    // renewal needs an offer of the renter and the same term from the rentee
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Periods(1),
+    };
+    let info = mock_info(rentee.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::NoRenewalOffer {});
+
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Periods(1),
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let status = query_lease_status(deps.as_ref(), env.clone(), 0).unwrap();
+    assert_eq!(status.renewal, Some(LeaseTerm::Periods(1)));
+
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Periods(2),
+    };
+    let info = mock_info(rentee.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::RenewalMismatch {});
+
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Periods(1),
+    };
+    let info = mock_info(rentee.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let ends = Expiration::AtHeight(env.block.height + period * 3);
+    let status = query_lease_status(deps.as_ref(), env.clone(), 0).unwrap();
+    assert_eq!(status.ends, Some(ends));
+    assert_eq!(status.renewal, None);
+
+    // the renewed period is paid as well
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // once the term is over the lease is closed and the deposit claimable
+    let mut ended = mock_env();
+    ended.block.height = env.block.height + period * 3;
+    let status = query_lease_status(deps.as_ref(), ended.clone(), 0).unwrap();
+    assert!(status.ended);
+
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    let err = execute(deps.as_mut(), ended.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::LeaseEnded {});
+
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 0 };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), ended.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRentee {});
+
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 0 };
+    let info = mock_info(rentee.as_str(), &[]);
+    let res = execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(3, &rentee, 200)]);
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
+    assert_eq!(q.rentee, None);
+    assert_eq!(q.escrow, Uint128::zero());
+
+    // a new request on an ended lease settles the deposit of the previous rentee
+    let until = Expiration::AtHeight(env.block.height + 100);
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: Some(LeaseTerm::Until(until)),
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let mut after = mock_env();
+    after.block.height = env.block.height + 100;
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info("new-rentee", &coins(400u128, "acudos"));
+    let res = execute(deps.as_mut(), after, info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(5, &rentee, 200)]);
+    let q = query_property_info(deps.as_ref(), 1).unwrap();
+    assert_eq!(q.rentee, Some(String::from("new-rentee")));
+    assert_eq!(q.term, None);
+    assert_eq!(q.ends, None);
+}
+
+#[test]
+fn fixed_term_settlement() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let period = 411428u64;
+    let seconds = 30 * 24 * 60 * 60;
+    for _ in 0..2 {
+        let info = mock_info(renter.as_str(), &[]);
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let env = mock_env();
+    let request = |id, rentee: &str, term| {
+        let msg = ExecuteMsg::RequestForLease {
+            property_id: id,
+            term: Some(term),
+            max_rate: None,
+            document: None,
+            offer: None,
+        };
+        (msg, mock_info(rentee, &coins(400u128, "acudos")))
+    };
+    let accept = |id| ExecuteMsg::AcceptLease {
+        property_id: id,
+        rent_schedule: None,
+        document: None,
+    };
+    let pay = |id| ExecuteMsg::PayRent {
+        property_id: id,
+        max_rate: None,
+    };
+
+    // a term too long to be paid is rejected
+    let (msg, info) = request(0, "time", LeaseTerm::Periods(u64::MAX));
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTerm {});
+
+    // a term ending at a time is paid in periods of time
+    let ends = Expiration::AtTime(env.block.time.plus_seconds(seconds * 2));
+    let (msg, info) = request(0, "time", LeaseTerm::Until(ends));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, accept(0)).unwrap();
+    let q = query_property_info(deps.as_ref(), 0).unwrap();
+    assert_eq!(
+        q.expires,
+        Some(Expiration::AtTime(env.block.time.plus_seconds(seconds)))
+    );
+    assert_eq!(q.lease_period, Some(Duration::Time(seconds)));
+
+    let info = mock_info("time", &coins(200u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, pay(0)).unwrap();
+    let info = mock_info("time", &coins(200u128, "acudos"));
+    let err = execute(deps.as_mut(), env.clone(), info, pay(0)).unwrap_err();
+    assert_eq!(err, ContractError::TermFullyPaid {});
+
+    // renewals have to end in time as well
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Until(Expiration::AtHeight(env.block.height + period * 5)),
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTerm {});
+
+    // paid up to the end, the lease is completed
+    let mut ended = mock_env();
+    ended.block.time = env.block.time.plus_seconds(seconds * 2);
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 0 };
+    let info = mock_info("time", &[]);
+    let res = execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(2, "time", 200)]);
+    let r = query_reputation(deps.as_ref(), String::from("time")).unwrap();
+    assert_eq!(r.stats.leases_completed, 1);
+    assert_eq!(r.stats.terminations_for_default, 0);
+
+    // a term that was not paid up to its end is closed as defaulted
+    let (msg, info) = request(1, "unpaid", LeaseTerm::Periods(2));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, accept(1)).unwrap();
+    let mut ended = mock_env();
+    ended.block.height = env.block.height + period * 2;
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 1 };
+    let info = mock_info("unpaid", &[]);
+    let res = execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(4, "unpaid", 200)]);
+    let r = query_reputation(deps.as_ref(), String::from("unpaid")).unwrap();
+    assert_eq!(r.stats.leases_completed, 0);
+    assert_eq!(r.stats.terminations_for_default, 1);
+
+    // so is one settled by the next request
+    let (msg, info) = request(1, "unpaid", LeaseTerm::Periods(2));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, accept(1)).unwrap();
+    let (msg, info) = request(1, "next", LeaseTerm::Periods(1));
+    execute(deps.as_mut(), ended, info, msg).unwrap();
+    let r = query_reputation(deps.as_ref(), String::from("unpaid")).unwrap();
+    assert_eq!(r.stats.terminations_for_default, 2);
+}
+
+#[test]
+fn terminate_paid_term() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let period = 411428u64;
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // a single period lease is paid up to its end once accepted
+    let env = mock_env();
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: Some(LeaseTerm::Periods(1)),
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info("rentee", &coins(400u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // terminated past its end and the grace window, it is completed, not defaulted
+    let mut later = mock_env();
+    later.block.height = env.block.height + period + period / 10 + 1;
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), later, info, msg).unwrap();
+    assert_eq!(res.messages, vec![payout(1, "rentee", 200)]);
+    let r = query_reputation(deps.as_ref(), String::from("rentee")).unwrap();
+    assert_eq!(r.stats.leases_completed, 1);
+    assert_eq!(r.stats.terminations_for_default, 0);
+    assert!(r.score >= 0);
+    assert_eq!(query_property_info(deps.as_ref(), 0).unwrap().rentee, None);
+}
+
+#[test]
+fn lease_lifecycle_hooks() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    let hook = String::from("insurance");
+    let period = 411428u64;
+
+    // only the admin manages hooks
+    let msg = ExecuteMsg::AddHook { addr: hook.clone() };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Hook(HookError::Admin(AdminError::NotAdmin {}))
+    );
+
+    let msg = ExecuteMsg::AddHook { addr: hook.clone() };
+    let info = mock_info(owner.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap();
+    let hooks: HooksResponse = from_binary(&res).unwrap();
+    assert_eq!(hooks.hooks, vec![hook.clone()]);
+
+    for _ in 0..2 {
+        let info = mock_info(renter.as_str(), &[]);
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    for (id, term) in [(0, Some(LeaseTerm::Periods(1))), (1, None)] {
+        let msg = ExecuteMsg::RequestForLease {
+            property_id: id,
+            term,
+            max_rate: None,
+            document: None,
+            offer: None,
+        };
+        let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        // requesting does not start a lease
+        assert!(res.messages.is_empty());
+    }
+    let hook_msg = |id, event| {
+        SubMsg::new(
+            LeaseChangedHookMsg::new(id, renter.clone(), rentee.clone(), event)
+                .into_cosmos_msg(hook.clone())
+                .unwrap(),
+        )
+    };
+
+    // accepting starts the lease
+    let env = mock_env();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages[1], hook_msg(0, LeaseEvent::Started));
+
+    // the renewal is reported once the rentee signed it
+    let msg = ExecuteMsg::RenewLease {
+        property_id: 0,
+        term: LeaseTerm::Periods(1),
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
+    assert!(res.messages.is_empty());
+    let info = mock_info(rentee.as_str(), &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![hook_msg(0, LeaseEvent::Renewed)]);
+
+    // claiming the deposit ends the lease once the renewed period is paid as well
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let mut ended = mock_env();
+    ended.block.height = env.block.height + period * 2;
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 0 };
+    let info = mock_info(rentee.as_str(), &[]);
+    let res = execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    assert_eq!(res.messages[1], hook_msg(0, LeaseEvent::Ended));
+
+    // terminating an unpaid lease reports a default
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::TerminateLease { property_id: 1 };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    assert_eq!(res.messages[1], hook_msg(1, LeaseEvent::Defaulted));
+
+    // removed hooks are not called anymore
+    let msg = ExecuteMsg::RemoveHook { addr: hook.clone() };
+    let info = mock_info(owner.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), ended.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let res = execute(deps.as_mut(), ended, info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn reputation() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    let period = 411428u64;
+    let reputation = |deps: cosmwasm_std::Deps, address: &str| -> ReputationResponse {
+        let msg = QueryMsg::Reputation {
+            address: address.to_string(),
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+
+    // unknown addresses start from zero
+    let r = reputation(deps.as_ref(), &rentee);
+    assert_eq!(r.stats, Reputation::default());
+    assert_eq!(r.score, 0);
+
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    // the second property asks for a non-negative score
+    let info = mock_info(renter.as_str(), &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: Some(0),
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let env = mock_env();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // one payment on time, one after the period expired
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let mut late = mock_env();
+    late.block.height = env.block.height + period * 2 + 10;
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    let res = execute(deps.as_mut(), late.clone(), info, msg).unwrap();
+    assert!(res.attributes.contains(&cosmwasm_std::attr("late", "true")));
+
+    // past the grace window rent is not accepted anymore and the renter terminates for default
+    let mut defaulted = mock_env();
+    defaulted.block.height = env.block.height + period * 3 + period / 10 + 1;
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    let err = execute(deps.as_mut(), defaulted.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Expired {});
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), defaulted.clone(), info, msg).unwrap();
+
+    let r = reputation(deps.as_ref(), &rentee);
+    assert_eq!(
+        r.stats,
+        Reputation {
+            on_time_payments: 1,
+            late_payments: 1,
+            terminations_for_default: 1,
+            ..Reputation::default()
+        }
+    );
+    assert_eq!(r.score, 1 - 2 - 5);
+
+    // too low to apply for the second property
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    let err = execute(deps.as_mut(), defaulted.clone(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ReputationTooLow {
+            score: -6,
+            required: 0
+        }
+    );
+
+    // a fresh applicant completes a fixed term lease there
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: Some(LeaseTerm::Periods(1)),
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info("new-rentee", &coins(400u128, "acudos"));
+    execute(deps.as_mut(), defaulted.clone(), info, msg).unwrap();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), defaulted.clone(), info, msg).unwrap();
+    let mut ended = mock_env();
+    ended.block.height = defaulted.block.height + period;
+    let msg = ExecuteMsg::ClaimDeposit { property_id: 1 };
+    let info = mock_info("new-rentee", &[]);
+    execute(deps.as_mut(), ended, info, msg).unwrap();
+    assert_eq!(
+        reputation(deps.as_ref(), "new-rentee")
+            .stats
+            .leases_completed,
+        1
+    );
+    assert_eq!(reputation(deps.as_ref(), &renter).stats.leases_completed, 1);
+
+    // disputes are recorded by the admin against a party of the lease
+    let msg = ExecuteMsg::RecordDisputeLost {
+        property_id: 1,
+        loser: renter.clone(),
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+    let msg = ExecuteMsg::RecordDisputeLost {
+        property_id: 1,
+        loser: String::from("someone"),
+    };
+    let info = mock_info(owner.as_str(), &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotAParty {});
+
+    let msg = ExecuteMsg::RecordDisputeLost {
+        property_id: 1,
+        loser: renter.clone(),
+    };
+    let info = mock_info(owner.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let r = reputation(deps.as_ref(), &renter);
+    assert_eq!(r.stats.disputes_lost, 1);
+    assert_eq!(r.score, 2 - 3);
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.lease_period, 411428);
+
+    // only the admin changes the config
+    let msg = ExecuteMsg::UpdateConfig {
+        lease_period: Some(1000),
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: None,
+    };
+    let info = mock_info("renter", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+    let zero = ExecuteMsg::UpdateConfig {
+        lease_period: Some(0),
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: None,
+    };
+    let info = mock_info(owner.as_str(), &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, zero).unwrap_err();
+    assert_eq!(err, ContractError::InvalidLeasePeriod {});
+
+    // a lease accepted before the change, its rent grows 10% every period
+    let renter = String::from("renter");
+    let rentee = String::from("rentee");
+    for _ in 0..2 {
+        let info = mock_info(renter.as_str(), &[]);
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap();
+    let msg_accept = |property_id| ExecuteMsg::AcceptLease {
+        property_id,
+        rent_schedule: Some(RentSchedule::Percentage {
+            increase: Decimal::percent(10),
+            every_periods: 1,
+        }),
+        document: None,
+    };
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg_accept(0)).unwrap();
+
+    let info = mock_info(owner.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.lease_period, 1000);
+
+    // the running lease keeps its period, for its rent and for payments
+    let env = mock_env();
+    let mut later = mock_env();
+    later.block.height += 1000 * 5;
+    let status = query_lease_status(deps.as_ref(), later.clone(), 0).unwrap();
+    assert_eq!(status.rent_due, Some(Uint128::new(200)));
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info(rentee.as_str(), &coins(200u128, "acudos"));
+    execute(deps.as_mut(), later.clone(), info, msg).unwrap();
+    let status = query_lease_status(deps.as_ref(), later, 0).unwrap();
+    let expires = Expiration::AtHeight(env.block.height + 411428 * 2);
+    assert_eq!(status.expires, Some(expires));
+
+    // leases accepted afterwards get the new period
+    let info = mock_info(rentee.as_str(), &coins(400u128, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(1)).unwrap();
+    let info = mock_info(renter.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info, msg_accept(1)).unwrap();
+    let property = query_property_info(deps.as_ref(), 1).unwrap();
+    assert_eq!(property.lease_period, Some(Duration::Height(1000)));
+    assert_eq!(
+        property.expires,
+        Some(Expiration::AtHeight(env.block.height + 1000))
+    );
+
+    // premiums are a share of the rent
+    for premium in [Decimal::zero(), Decimal::one()] {
+        let msg = ExecuteMsg::UpdateInsurance {
+            insurance: Some(InsuranceInfo {
+                pool: String::from("pool"),
+                premium,
+            }),
+        };
+        let info = mock_info(owner.as_str(), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPremium {});
+    }
+}
+
+#[test]
+fn bulk_operations() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = String::from("owner");
+    do_instantiate(deps.as_mut(), &owner);
+    let msg = ExecuteMsg::UpdateConfig {
+        lease_period: None,
+        lease_period_seconds: None,
+        max_batch_size: Some(3),
+        pull_rent: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), msg).unwrap();
+
+    let unit = |rent: u128| NewProperty {
+        rent: Uint128::new(rent),
+        min_reputation: None,
+        currency: None,
+    };
+    let renter = mock_info("renter", &[]);
+
+    // batches are limited by the config
+    let msg = ExecuteMsg::AddProperties { properties: vec![] };
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::EmptyBatch {});
+    let msg = ExecuteMsg::AddProperties {
+        properties: (1..=4).map(|i| unit(100 * i)).collect(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::BatchTooLarge { size: 4, max: 3 });
+
+    // one event per listed unit
+    let msg = ExecuteMsg::AddProperties {
+        properties: (1..=3).map(|i| unit(100 * i)).collect(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(res.events[2].ty, "add_property");
+    assert_eq!(res.events[2].attributes[0].value, "2");
+    assert_eq!(query_get_total_property(deps.as_ref()).unwrap(), 3);
+    assert_eq!(
+        query_property_info(deps.as_ref(), 2).unwrap().rent,
+        Uint128::new(300)
+    );
+
+    // a request locks the rent of property 1, so the whole update fails
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info("rentee", &coins(400, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let update = |property_id, rent| RentUpdate {
+        property_id,
+        rent: Uint128::new(rent),
+    };
+    let msg = ExecuteMsg::BulkUpdateRent {
+        updates: vec![update(0, 150), update(1, 250)],
+    };
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::IsRented {});
+    let msg = ExecuteMsg::BulkUpdateRent {
+        updates: vec![update(0, 150), update(2, 350)],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other", &[]),
+        msg.clone(),
+    );
+    assert_eq!(err.unwrap_err(), ContractError::InvalidRenter {});
+    let res = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(
+        query_property_info(deps.as_ref(), 0).unwrap().rent,
+        Uint128::new(150)
+    );
+
+    // the first rents are released in one transfer
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let info = mock_info("rentee2", &coins(300, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::BulkAccept {
+        property_ids: vec![0, 1],
+    };
+    let res = execute(deps.as_mut(), mock_env(), renter, msg).unwrap();
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "accept_lease");
+    assert_eq!(res.messages, vec![payout(0, "renter", 350)]);
+    let status = query_lease_status(deps.as_ref(), mock_env(), 1).unwrap();
+    assert_eq!(status.rentee, Some(String::from("rentee")));
+    assert!(status.expires.is_some());
+}
+
+#[test]
+fn property_managers() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let renter = mock_info("renter", &[]);
+    let manager = mock_info("manager", &[]);
+    let unit = NewProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    let msg = ExecuteMsg::AddProperties {
+        properties: vec![unit.clone(), unit],
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    for (property_id, rentee) in [(0, "rentee"), (1, "rentee2")] {
+        let msg = ExecuteMsg::RequestForLease {
+            property_id,
+            term: None,
+            max_rate: None,
+            document: None,
+            offer: None,
+        };
+        let info = mock_info(rentee, &coins(200, "acudos"));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    let grant = |property_id, permissions: Vec<Permission>, expires| ExecuteMsg::GrantManager {
+        manager: String::from("manager"),
+        property_id,
+        permissions,
+        expires,
+    };
+    let height = mock_env().block.height;
+
+    // grants need permissions, must not be expired and only cover own properties
+    let msg = grant(None, vec![], None);
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidGrant {});
+    let msg = grant(
+        None,
+        vec![Permission::Terminate],
+        Some(Expiration::AtHeight(height)),
+    );
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidGrant {});
+    let msg = grant(Some(0), vec![Permission::AcceptReject], None);
+    let err = execute(deps.as_mut(), mock_env(), mock_info("other", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenter {});
+
+    // the manager accepts on property 0 only, the first rent goes to the renter
+    let msg = grant(Some(0), vec![Permission::AcceptReject], None);
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let accept = |property_id| ExecuteMsg::AcceptLease {
+        property_id,
+        rent_schedule: None,
+        document: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), manager.clone(), accept(0)).unwrap();
+    assert_eq!(res.messages, vec![payout(0, "renter", 100)]);
+    let err = execute(deps.as_mut(), mock_env(), manager.clone(), accept(1)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenter {});
+
+    // a grant for all properties, scoped to terminations and limited in time
+    let msg = grant(
+        None,
+        vec![Permission::Terminate],
+        Some(Expiration::AtHeight(height + 10)),
+    );
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RejectLease { property_id: 1 };
+    let err = execute(deps.as_mut(), mock_env(), manager.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenter {});
+    let msg = ExecuteMsg::BulkUpdateRent {
+        updates: vec![RentUpdate {
+            property_id: 1,
+            rent: Uint128::new(150),
+        }],
+    };
+    let err = execute(deps.as_mut(), mock_env(), manager.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenter {});
+
+    let permissions = |deps: Deps, env| {
+        query_permissions(deps, env, String::from("renter"), String::from("manager")).unwrap()
+    };
+    let res = permissions(deps.as_ref(), mock_env());
+    assert_eq!(
+        res.all_properties.map(|g| g.permissions),
+        Some(vec![Permission::Terminate])
+    );
+    assert_eq!(res.properties.len(), 1);
+    assert_eq!(res.properties[0].0, 0);
+
+    // once the grant expired, the manager cannot terminate the defaulted lease
+    let mut env = mock_env();
+    env.block.height += 411429;
+    let res = permissions(deps.as_ref(), env.clone());
+    assert_eq!(res.all_properties, None);
+    let msg = ExecuteMsg::TerminateLease { property_id: 0 };
+    let err = execute(deps.as_mut(), env, manager.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenter {});
+
+    // revoked grants are gone
+    let revoke = ExecuteMsg::RevokeManager {
+        manager: String::from("manager"),
+        property_id: Some(0),
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), revoke.clone()).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), renter, revoke).unwrap_err();
+    assert_eq!(err, ContractError::NoGrant {});
+    assert!(permissions(deps.as_ref(), mock_env()).properties.is_empty());
+}
+
+#[test]
+fn lease_documents() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let renter = mock_info("renter", &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg.clone()).unwrap();
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+
+    let signed = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+    let document = |hash: &str, uri: Option<&str>| LeaseDocument {
+        hash: hash.to_string(),
+        uri: uri.map(String::from),
+    };
+    let request = |property_id, document| ExecuteMsg::RequestForLease {
+        property_id,
+        term: None,
+        max_rate: None,
+        document,
+        offer: None,
+    };
+    let rentee = mock_info("rentee", &coins(200, "acudos"));
+
+    let msg = request(0, Some(document("not a hash", None)));
+    let err = execute(deps.as_mut(), mock_env(), rentee.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidDocumentHash {});
+    let msg = request(0, Some(document(signed, Some("ipfs://lease.pdf"))));
+    execute(deps.as_mut(), mock_env(), rentee.clone(), msg).unwrap();
+    let msg = request(1, None);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("rentee2", &coins(200, "acudos")),
+        msg,
+    )
+    .unwrap();
+
+    // the renter has to sign the same document before the first rent is released
+    let accept = |property_id, document| ExecuteMsg::AcceptLease {
+        property_id,
+        rent_schedule: None,
+        document,
+    };
+    let other = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+    for document in [None, Some(document(other, None))] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            renter.clone(),
+            accept(0, document),
+        );
+        assert_eq!(err.unwrap_err(), ContractError::DocumentMismatch {});
+    }
+    let msg = ExecuteMsg::BulkAccept {
+        property_ids: vec![0],
+    };
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::DocumentMismatch {});
+    // nor can the renter bind a lease the rentee did not sign a document for
+    let msg = accept(1, Some(document(other, None)));
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::DocumentMismatch {});
+
+    // hashes are compared case insensitive
+    let msg = accept(0, Some(document(&signed.to_lowercase(), None)));
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let status = query_lease_status(deps.as_ref(), mock_env(), 0).unwrap();
+    assert_eq!(
+        status.document,
+        Some(document(&signed.to_lowercase(), Some("ipfs://lease.pdf")))
+    );
+
+    // offers are bound the same way, whether the renter accepts or counters them
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 2,
+        term: None,
+        max_rate: None,
+        document: Some(document(signed, None)),
+        offer: Some(RentOffer {
+            rent: Uint128::new(80),
+            expires: None,
+        }),
+    };
+    let applicant = mock_info("rentee3", &coins(160, "acudos"));
+    execute(deps.as_mut(), mock_env(), applicant, msg).unwrap();
+    let msg = ExecuteMsg::AcceptOffer {
+        property_id: 2,
+        applicant: "rentee3".to_string(),
+        document: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::DocumentMismatch {});
+    let counter = |document| ExecuteMsg::CounterOffer {
+        property_id: 2,
+        applicant: "rentee3".to_string(),
+        rent: Uint128::new(90),
+        document,
+    };
+    let accept_counter = ExecuteMsg::AcceptCounter {
+        property_id: 2,
+        max_rate: None,
+    };
+    let applicant = mock_info("rentee3", &coins(20, "acudos"));
+    let msg = counter(Some(document(other, None)));
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        applicant.clone(),
+        accept_counter.clone(),
+    );
+    assert_eq!(err.unwrap_err(), ContractError::DocumentMismatch {});
+    let msg = counter(Some(document(signed, None)));
+    execute(deps.as_mut(), mock_env(), renter, msg).unwrap();
+    execute(deps.as_mut(), mock_env(), applicant, accept_counter).unwrap();
+    let status = query_lease_status(deps.as_ref(), mock_env(), 2).unwrap();
+    assert_eq!(
+        status.document,
+        Some(document(&signed.to_lowercase(), None))
+    );
+}
+
+#[test]
+fn pause_every_handler() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = mock_info("owner", &[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg).unwrap();
+
+    // only the admin pauses, and withdraws only once everything is paused
+    let pause = ExecuteMsg::Pause { actions: None };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("renter", &[]),
+        pause.clone(),
+    );
+    assert_eq!(
+        err.unwrap_err(),
+        ContractError::Admin(AdminError::NotAdmin {})
+    );
+    let msg = ExecuteMsg::EmergencyWithdraw { limit: None };
+    let err = execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::NotPaused {});
+    execute(deps.as_mut(), mock_env(), owner.clone(), pause).unwrap();
+
+    let mut pausable = vec![
+        (
+            LeaseAction::AddProperty,
+            ExecuteMsg::AddProperty {
+                rent: Uint128::new(200),
+                min_reputation: None,
+                currency: None,
+            },
+        ),
+        (
+            LeaseAction::AddProperty,
+            ExecuteMsg::AddProperties { properties: vec![] },
+        ),
+        (
+            LeaseAction::UpdateRent,
+            ExecuteMsg::BulkUpdateRent { updates: vec![] },
+        ),
+        (
+            LeaseAction::UpdateRent,
+            ExecuteMsg::SetDailyRate {
+                property_id: 0,
+                daily_rate: None,
+            },
+        ),
+        (
+            LeaseAction::AcceptLease,
+            ExecuteMsg::BulkAccept {
+                property_ids: vec![0],
+            },
+        ),
+        (
+            LeaseAction::AcceptLease,
+            ExecuteMsg::AcceptOffer {
+                property_id: 0,
+                applicant: String::from("rentee"),
+                document: None,
+            },
+        ),
+        (
+            LeaseAction::AcceptLease,
+            ExecuteMsg::AcceptCounter {
+                property_id: 0,
+                max_rate: None,
+            },
+        ),
+        (
+            LeaseAction::RejectLease,
+            ExecuteMsg::RejectOffer {
+                property_id: 0,
+                applicant: String::from("rentee"),
+            },
+        ),
+        (LeaseAction::ClaimPayout, ExecuteMsg::ClaimPayout {}),
+        (LeaseAction::Withdraw, ExecuteMsg::Withdraw { amount: None }),
+        (LeaseAction::ClaimUnbonded, ExecuteMsg::ClaimUnbonded {}),
+        (
+            LeaseAction::Booking,
+            ExecuteMsg::BookProperty {
+                property_id: 0,
+                start: Expiration::AtTime(mock_env().block.time),
+                end: Expiration::AtTime(mock_env().block.time.plus_seconds(60)),
+            },
+        ),
+        (
+            LeaseAction::Booking,
+            ExecuteMsg::CancelBooking {
+                property_id: 0,
+                start: Expiration::AtTime(mock_env().block.time),
+            },
+        ),
+        (
+            LeaseAction::Booking,
+            ExecuteMsg::CloseBooking {
+                property_id: 0,
+                start: Expiration::AtTime(mock_env().block.time),
+            },
+        ),
+        (
+            LeaseAction::Offer,
+            ExecuteMsg::CounterOffer {
+                property_id: 0,
+                applicant: String::from("rentee"),
+                rent: Uint128::new(200),
+                document: None,
+            },
+        ),
+        (
+            LeaseAction::Offer,
+            ExecuteMsg::WithdrawOffer { property_id: 0 },
+        ),
+        (
+            LeaseAction::GrantManager,
+            ExecuteMsg::GrantManager {
+                manager: String::from("manager"),
+                property_id: None,
+                permissions: vec![Permission::Terminate],
+                expires: None,
+            },
+        ),
+    ];
+    let by_id = [
+        LeaseAction::AcceptLease,
+        LeaseAction::RequestForLease,
+        LeaseAction::TerminateLease,
+        LeaseAction::PayRent,
+        LeaseAction::RejectLease,
+        LeaseAction::RenewLease,
+        LeaseAction::ClaimDeposit,
+    ];
+    pausable.extend(by_id.iter().copied().zip(messages_for_id(0)));
+
+    for (action, msg) in &pausable {
+        let info = mock_info("renter", &coins(400, "acudos"));
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Paused { action: *action });
+    }
+
+    // admin messages, the landlord group hook and revoking managers stay available. Sent
+    // by the renter they fail, but not for the pause.
+    let open = vec![
+        ExecuteMsg::UpdateAdmin { admin: None },
+        ExecuteMsg::AddHook {
+            addr: String::from("hook"),
+        },
+        ExecuteMsg::RemoveHook {
+            addr: String::from("hook"),
+        },
+        ExecuteMsg::RecordDisputeLost {
+            property_id: 0,
+            loser: String::from("rentee"),
+        },
+        ExecuteMsg::UpdateLandlordGroup {
+            landlord_group: None,
+        },
+        ExecuteMsg::MemberChangedHook(MemberChangedHookMsg { diffs: vec![] }),
+        ExecuteMsg::UpdateInsurance { insurance: None },
+        ExecuteMsg::UpdateOracle { oracle: None },
+        ExecuteMsg::UpdateStaking { staking: None },
+        ExecuteMsg::Pause { actions: None },
+        ExecuteMsg::Unpause { actions: None },
+        ExecuteMsg::EmergencyWithdraw { limit: None },
+    ];
+    for msg in open {
+        let res = execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg);
+        assert!(!matches!(res, Err(ContractError::Paused { .. })));
+    }
+    let msg = ExecuteMsg::UpdateConfig {
+        lease_period: Some(1000),
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: None,
+    };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RevokeManager {
+        manager: String::from("manager"),
+        property_id: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::NoGrant {});
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap();
+    let status: PauseStatusResponse = from_binary(&res).unwrap();
+    assert!(status.all);
+
+    // resuming a single action does not lift the pause of everything
+    let msg = ExecuteMsg::Unpause {
+        actions: Some(vec![LeaseAction::RequestForLease]),
+    };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    let info = mock_info("rentee", &coins(400, "acudos"));
+    let err = execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            action: LeaseAction::RequestForLease
+        }
+    );
+
+    // pause a single action
+    let msg = ExecuteMsg::Unpause { actions: None };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    let msg = ExecuteMsg::Pause {
+        actions: Some(vec![LeaseAction::RequestForLease]),
+    };
+    execute(deps.as_mut(), mock_env(), owner, msg).unwrap();
+    let info = mock_info("rentee", &coins(400, "acudos"));
+    let err = execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            action: LeaseAction::RequestForLease
+        }
+    );
+    let (_, msg) = &pausable[0];
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("renter", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap();
+    let status: PauseStatusResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        status,
+        PauseStatusResponse {
+            all: false,
+            actions: vec![LeaseAction::RequestForLease]
+        }
+    );
+}
+
+#[test]
+fn emergency_withdraw() {
+    // the contract holds 250acudos, but owes 500acudos of escrow
+    let mut deps = mock_dependencies(&coins(250, "acudos"));
+    let owner = mock_info("owner", &[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let renter = mock_info("renter", &[]);
+    for rent in [200, 100] {
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(rent),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    }
+    let info = mock_info("rentee", &coins(400, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap();
+    let info = mock_info("rentee2", &coins(200, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(1)).unwrap();
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter, msg).unwrap();
+
+    let msg = ExecuteMsg::Pause { actions: None };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+    // one escrow per call, both rentees get half of their escrow
+    let msg = ExecuteMsg::EmergencyWithdraw { limit: Some(1) };
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages, vec![payout(1, "rentee", 200)]);
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("done", "false")));
+
+    // the pause stays until the withdrawal is done
+    let unpause = ExecuteMsg::Unpause { actions: None };
+    let err = execute(deps.as_mut(), mock_env(), owner.clone(), unpause.clone()).unwrap_err();
+    assert_eq!(err, ContractError::WithdrawalInProgress {});
+
+    // later calls refund at the ratio counted by the first one
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages, vec![payout(2, "rentee2", 50)]);
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert!(res.attributes.contains(&cosmwasm_std::attr("done", "true")));
+    execute(deps.as_mut(), mock_env(), owner, unpause).unwrap();
+    // the pending request is closed, the accepted lease goes on without deposit
+    let requested = query_property_info(deps.as_ref(), 0).unwrap();
+    assert_eq!(requested.rentee, None);
+    let leased = query_property_info(deps.as_ref(), 1).unwrap();
+    assert_eq!(leased.rentee, Some(String::from("rentee2")));
+    assert_eq!(leased.escrow, Uint128::zero());
+
+    // closing the lease later pays no deposit of zero
+    let mut env = mock_env();
+    env.block.height += 1_000_000;
+    let msg = ExecuteMsg::TerminateLease { property_id: 1 };
+    let res = execute(deps.as_mut(), env, mock_info("renter", &[]), msg).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn escrow_total() {
+    // the running total matches the escrows it counts after every write
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let renter = mock_info("renter", &[]);
+    for _ in 0..2 {
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(100),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    }
+    let msg = ExecuteMsg::SetDailyRate {
+        property_id: 0,
+        daily_rate: Some(Uint128::new(10)),
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let assert_total = |deps: Deps, expected: u128| {
+        let escrows = properties()
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.unwrap().1.escrow)
+            .chain(
+                offers()
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|item| item.unwrap().1.escrow),
+            )
+            .chain(
+                BOOKINGS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|item| item.unwrap().1.escrow),
+            )
+            .fold(Uint128::zero(), |total, escrow| total + escrow);
+        assert_eq!(escrows, Uint128::new(expected));
+        assert_eq!(load_total(deps.storage, &ESCROW_TOTAL).unwrap(), escrows);
+    };
+
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: Some(RentOffer {
+            rent: Uint128::new(80),
+            expires: None,
+        }),
+    };
+    let info = mock_info("rentee", &coins(160, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_total(deps.as_ref(), 160);
+
+    let start = Expiration::AtTime(mock_env().block.time.plus_seconds(24 * 60 * 60));
+    let msg = ExecuteMsg::BookProperty {
+        property_id: 0,
+        start,
+        end: Expiration::AtTime(mock_env().block.time.plus_seconds(2 * 24 * 60 * 60)),
+    };
+    let guest = mock_info("guest", &coins(10, "acudos"));
+    execute(deps.as_mut(), mock_env(), guest.clone(), msg).unwrap();
+    assert_total(deps.as_ref(), 170);
+    let msg = ExecuteMsg::CancelBooking {
+        property_id: 0,
+        start,
+    };
+    execute(deps.as_mut(), mock_env(), guest, msg).unwrap();
+    assert_total(deps.as_ref(), 160);
+    let msg = ExecuteMsg::WithdrawOffer { property_id: 0 };
+    execute(deps.as_mut(), mock_env(), mock_info("rentee", &[]), msg).unwrap();
+    assert_total(deps.as_ref(), 0);
+
+    // the first rent leaves the escrow on acceptance, the deposit when the lease is closed
+    let info = mock_info("rentee2", &coins(200, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(1)).unwrap();
+    assert_total(deps.as_ref(), 200);
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 1,
+        rent_schedule: None,
+        document: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    assert_total(deps.as_ref(), 100);
+    let mut env = mock_env();
+    env.block.height += 1_000_000;
+    let msg = ExecuteMsg::TerminateLease { property_id: 1 };
+    execute(deps.as_mut(), env, renter, msg).unwrap();
+    assert_total(deps.as_ref(), 0);
+}
+
+#[test]
+fn pull_rent() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = mock_info("owner", &[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let msg = ExecuteMsg::UpdateConfig {
+        lease_period: None,
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: Some(true),
+    };
+    execute(deps.as_mut(), mock_env(), owner, msg).unwrap();
+
+    let renter = mock_info("renter", &[]);
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let info = mock_info("rentee", &coins(400, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(0)).unwrap();
+
+    // the first rent and every rent paid accrue instead of being sent
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info("rentee", &coins(200, "acudos"));
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    let balance = |deps: Deps, address: &str| {
+        let msg = QueryMsg::Balance {
+            address: address.to_string(),
+        };
+        let res = query(deps, mock_env(), msg).unwrap();
+        from_binary::<BalanceResponse>(&res).unwrap().balance
+    };
+    assert_eq!(balance(deps.as_ref(), "renter"), coins(400, "acudos"));
+
+    let withdraw = |amount: Option<Vec<Coin>>| ExecuteMsg::Withdraw { amount };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("rentee", &[]),
+        withdraw(None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        renter.clone(),
+        withdraw(Some(coins(300, "acudos"))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from("renter"),
+            amount: coins(300, "acudos"),
+        })]
+    );
+    assert_eq!(balance(deps.as_ref(), "renter"), coins(100, "acudos"));
+
+    // only what accrued can be withdrawn
+    for amount in [coins(200, "acudos"), coins(1, "ucudos")] {
+        let msg = withdraw(Some(amount));
+        let err = execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientBalance {});
+    }
+
+    let res = execute(deps.as_mut(), mock_env(), renter.clone(), withdraw(None)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from("renter"),
+            amount: coins(100, "acudos"),
+        })]
+    );
+    assert_eq!(balance(deps.as_ref(), "renter"), vec![]);
+    let err = execute(deps.as_mut(), mock_env(), renter, withdraw(None)).unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {});
+}
+
+#[test]
+fn overview_queries() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let renter = mock_info("renter", &[]);
+    for _ in 0..3 {
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    }
+    for id in [0, 1] {
+        let info = mock_info("rentee", &coins(400, "acudos"));
+        execute(deps.as_mut(), mock_env(), info, msg_request(id)).unwrap();
+    }
+    let msg = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    execute(deps.as_mut(), mock_env(), renter.clone(), msg).unwrap();
+    let msg = ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate: None,
+    };
+    let info = mock_info("rentee", &coins(200, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::RequestForLease {
+        property_id: 2,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: Some(RentOffer {
+            rent: Uint128::new(150),
+            expires: None,
+        }),
+    };
+    let info = mock_info("rentee", &coins(300, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let page = |deps: Deps, start_after, limit| -> TenantOverviewResponse {
+        let msg = QueryMsg::TenantOverview {
+            address: String::from("rentee"),
+            start_after,
+            limit,
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+    let tenant = |deps: Deps| page(deps, None, None);
+    let overview = tenant(deps.as_ref());
+    let expires = Expiration::AtHeight(mock_env().block.height + 2 * 411428);
+    assert_eq!(overview.leases.len(), 1);
+    assert_eq!(overview.leases[0].property_id, 0);
+    assert_eq!(overview.leases[0].rent_due, Uint128::new(200));
+    assert_eq!(overview.leases[0].expires, expires);
+    assert_eq!(overview.applications.len(), 1);
+    assert_eq!(overview.applications[0].property_id, 1);
+    assert_eq!(overview.offers.len(), 1);
+    assert_eq!(overview.offers[0].property_id, 2);
+    assert_eq!(overview.offers[0].offer.rent, Uint128::new(150));
+    // the deposit of the lease and the locked funds of the request and the offer
+    assert_eq!(overview.escrow, Uint128::new(900));
+    assert_eq!(overview.next_due, Some(expires));
+    // the first rent and one more period
+    assert_eq!(overview.paid, Uint128::new(400));
+
+    // pages go through leases, requests and offers by property id
+    let overview = page(deps.as_ref(), None, Some(2));
+    assert_eq!(overview.leases.len(), 1);
+    assert_eq!(overview.applications.len(), 1);
+    assert_eq!(overview.offers, vec![]);
+    let overview = page(deps.as_ref(), Some(1), Some(2));
+    assert_eq!(overview.leases, vec![]);
+    assert_eq!(overview.applications, vec![]);
+    assert_eq!(overview.offers.len(), 1);
+    assert_eq!(overview.escrow, Uint128::new(300));
+
+    let msg = QueryMsg::LandlordOverview {
+        address: String::from("renter"),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    let overview: LandlordOverviewResponse = from_binary(&res).unwrap();
+    assert_eq!(overview.properties, 3);
+    assert_eq!(overview.leases.len(), 1);
+    assert_eq!(overview.leases[0].rentee, "rentee");
+    assert_eq!(overview.applications.len(), 1);
+    assert_eq!(overview.escrow, Uint128::new(600));
+    assert_eq!(overview.next_due, Some(expires));
+    assert_eq!(overview.received, Uint128::new(400));
+
+    // the indexes follow the rentee of a property
+    let msg = ExecuteMsg::RejectLease { property_id: 1 };
+    execute(deps.as_mut(), mock_env(), renter, msg).unwrap();
+    let overview = tenant(deps.as_ref());
+    assert_eq!(overview.applications, vec![]);
+    assert_eq!(overview.escrow, Uint128::new(500));
+}
+
+#[test]
+fn search_properties() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let listings = [
+        ("renter", 300),
+        ("renter", 100),
+        ("renter", 200),
+        ("renter2", 150),
+        ("renter2", 100),
+    ];
+    for (renter, rent) in listings {
+        let msg = ExecuteMsg::AddProperty {
+            rent: Uint128::new(rent),
+            min_reputation: None,
+            currency: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(renter, &[]), msg).unwrap();
+    }
+    // requested properties are not open anymore
+    let info = mock_info("rentee", &coins(200, "acudos"));
+    execute(deps.as_mut(), mock_env(), info, msg_request(1)).unwrap();
+
+    let search = |deps: Deps,
+                  min_rent: Option<u128>,
+                  max_rent: Option<u128>,
+                  renter: Option<&str>,
+                  start_after: Option<u64>,
+                  limit: Option<u32>| {
+        let msg = QueryMsg::SearchProperties {
+            min_rent: min_rent.map(Uint128::new),
+            max_rent: max_rent.map(Uint128::new),
+            denom: None,
+            renter: renter.map(String::from),
+            start_after,
+            limit,
+        };
+        let res = query(deps, mock_env(), msg).unwrap();
+        let res: SearchPropertiesResponse = from_binary(&res).unwrap();
+        res.properties
+            .into_iter()
+            .map(|p| p.property_id)
+            .collect::<Vec<u64>>()
+    };
+
+    // ordered by rent, then by id
+    assert_eq!(
+        search(deps.as_ref(), None, None, None, None, None),
+        vec![4, 3, 2, 0]
+    );
+    assert_eq!(
+        search(deps.as_ref(), Some(150), Some(200), None, None, None),
+        vec![3, 2]
+    );
+    assert_eq!(
+        search(deps.as_ref(), Some(101), Some(149), None, None, None),
+        Vec::<u64>::new()
+    );
+
+    // pages continue after the last id
+    assert_eq!(
+        search(deps.as_ref(), None, None, None, None, Some(2)),
+        vec![4, 3]
+    );
+    assert_eq!(
+        search(deps.as_ref(), None, None, None, Some(3), Some(2)),
+        vec![2, 0]
+    );
+    assert_eq!(
+        search(deps.as_ref(), Some(200), None, None, Some(4), None),
+        vec![2, 0]
+    );
+
+    // the listings of a renter, ordered by id
+    assert_eq!(
+        search(deps.as_ref(), None, None, Some("renter"), None, None),
+        vec![0, 2]
+    );
+    assert_eq!(
+        search(deps.as_ref(), None, Some(120), Some("renter2"), None, None),
+        vec![4]
+    );
+    assert_eq!(
+        search(deps.as_ref(), None, None, Some("renter"), Some(0), None),
+        vec![2]
+    );
+
+    // the empty currency of rented properties cannot be listed or searched
+    let msg = ExecuteMsg::AddProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: Some(String::new()),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCurrency {});
+    let msg = QueryMsg::SearchProperties {
+        min_rent: None,
+        max_rent: None,
+        denom: Some(String::new()),
+        renter: None,
+        start_after: None,
+        limit: None,
+    };
+    query(deps.as_ref(), mock_env(), msg).unwrap_err();
+
+    // a rejected request opens the property again
+    let msg = ExecuteMsg::RejectLease { property_id: 1 };
+    execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg).unwrap();
+    assert_eq!(
+        search(deps.as_ref(), None, Some(100), None, None, None),
+        vec![1, 4]
+    );
+}
+
+fn msg_request(property_id: u64) -> ExecuteMsg {
+    ExecuteMsg::RequestForLease {
+        property_id,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    }
+}
+
+#[test]
+fn snake_case_messages() {
+    let mut deps = mock_dependencies(&[]);
+    do_instantiate(deps.as_mut(), "owner");
+    let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.contract, "crates.io:lease-management-system");
+
+    let msg: ExecuteMsg = from_slice(br#"{"add_property":{"rent":"200"}}"#).unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::AddProperty {
+            rent: Uint128::new(200),
+            min_reputation: None,
+            currency: None,
+        }
+    );
+    execute(deps.as_mut(), mock_env(), mock_info("renter", &[]), msg).unwrap();
+
+    let msg: ExecuteMsg =
+        from_slice(br#"{"request_for_lease":{"property_id":0,"term":{"periods":2}}}"#).unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::RequestForLease {
+            property_id: 0,
+            term: Some(LeaseTerm::Periods(2)),
+            max_rate: None,
+            document: None,
+            offer: None,
+        }
+    );
+
+    // messages are written in snake_case
+    let json = cosmwasm_std::to_vec(&QueryMsg::GetTotalProperties).unwrap();
+    assert_eq!(json, br#""get_total_properties""#.to_vec());
+
+    // the query entry point only takes deps, env and the message
+    let msg: QueryMsg = from_slice(br#"{"property_detail":0}"#).unwrap();
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+    let res: PropertyResponse = from_binary(&res).unwrap();
+    assert_eq!(res.property.rent, Uint128::new(200));
+}
+
+#[test]
+fn legacy_pascal_case_messages() {
+    // the PascalCase names of 0.1 are accepted until 0.3
+    let msg: ExecuteMsg =
+        from_slice(br#"{"RequestForLease":{"property_id":0,"term":{"until":{"at_height":100}}}}"#)
+            .unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::RequestForLease {
+            property_id: 0,
+            term: Some(LeaseTerm::Until(Expiration::AtHeight(100))),
+            max_rate: None,
+            document: None,
+            offer: None,
+        }
+    );
+    let msg: ExecuteMsg = from_slice(br#"{"PayRent":{"property_id":3}}"#).unwrap();
+    assert_eq!(
+        msg,
+        ExecuteMsg::PayRent {
+            property_id: 3,
+            max_rate: None,
+        }
+    );
+
+    let msg: QueryMsg = from_slice(br#""ShowAllAvailableProperties""#).unwrap();
+    assert_eq!(msg, QueryMsg::ShowAllAvailableProperties);
+    let msg: QueryMsg = from_slice(br#"{"PropertyDetail":1}"#).unwrap();
+    assert_eq!(msg, QueryMsg::PropertyDetail(1));
+}
+
+// every message addressing a property by id
+fn messages_for_id(id: u64) -> Vec<ExecuteMsg> {
+    vec![
+        ExecuteMsg::AcceptLease {
+            property_id: id,
+            rent_schedule: None,
+            document: None,
+        },
+        ExecuteMsg::RequestForLease {
+            property_id: id,
+            term: None,
+            max_rate: None,
+            document: None,
+            offer: None,
+        },
+        ExecuteMsg::TerminateLease { property_id: id },
+        ExecuteMsg::PayRent {
+            property_id: id,
+            max_rate: None,
+        },
+        ExecuteMsg::RejectLease { property_id: id },
+        ExecuteMsg::RenewLease {
+            property_id: id,
+            term: LeaseTerm::Periods(1),
+        },
+        ExecuteMsg::ClaimDeposit { property_id: id },
+    ]
+}
+
+proptest! {
+    #[test]
+    fn unknown_ids_are_not_found(listed in 0u64..5, offset in any::<u64>()) {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut(), "owner");
+        for _ in 0..listed {
+            let info = mock_info("renter", &[]);
+            let msg = ExecuteMsg::AddProperty {
+                rent: Uint128::new(200),
+                min_reputation: None,
+                currency: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+        let id = listed.saturating_add(offset);
+        let not_found = || StdError::NotFound {
+            kind: String::from("property not found"),
+        };
+
+        for msg in messages_for_id(id) {
+            let info = mock_info("rentee", &coins(400u128, "acudos"));
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            prop_assert_eq!(err, ContractError::NotFound {});
+        }
+        prop_assert_eq!(query_property_info(deps.as_ref(), id).unwrap_err(), not_found());
+        prop_assert_eq!(
+            query_property_at_height(deps.as_ref(), id, 12_345).unwrap_err(),
+            not_found()
+        );
+        prop_assert_eq!(query_lease_status(deps.as_ref(), mock_env(), id).unwrap_err(), not_found());
+    }
+
+    #[test]
+    fn ids_are_sequential(rents in prop::collection::vec(1u128..1_000_000, 1..20)) {
+        let mut deps = mock_dependencies(&[]);
+        do_instantiate(deps.as_mut(), "owner");
+        for rent in &rents {
+            let info = mock_info("renter", &[]);
+            let msg = ExecuteMsg::AddProperty {
+                rent: Uint128::new(*rent),
+                min_reputation: None,
+                currency: None,
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+        prop_assert_eq!(query_get_total_property(deps.as_ref()).unwrap(), rents.len() as u64);
+        for (id, rent) in rents.iter().enumerate() {
+            let property = query_property_info(deps.as_ref(), id as u64).unwrap();
+            prop_assert_eq!(property.rent, Uint128::new(*rent));
+        }
+    }
+
+    #[test]
+    fn ids_round_trip_as_u64(id in any::<u64>()) {
+        for msg in messages_for_id(id) {
+            let json = cosmwasm_std::to_vec(&msg).unwrap();
+            let parsed: ExecuteMsg = cosmwasm_std::from_slice(&json).unwrap();
+            prop_assert_eq!(parsed, msg);
+        }
+    }
+}
@@ -0,0 +1,1583 @@
+use cosmwasm_std::{
+    coins, to_binary, Addr, CosmosMsg, Decimal, Empty, StdError, Uint128, Validator, WasmMsg,
+};
+use cw0::{Duration, Expiration};
+use cw3::{Status, Vote};
+use cw4::Member;
+use cw_controllers::{Claim, ClaimsResponse};
+use cw_multi_test::test_helpers::{contracts::oracle, EmptyMsg};
+use cw_multi_test::{
+    next_block, App, AppBuilder, Contract, ContractWrapper, Executor, StakeKeeper, BONDED_POOL,
+};
+
+use lease::LeaseContract;
+use lease_management_system::msg::{
+    AvailabilityResponse, BookingsResponse, ClaimablePayoutResponse, ConfigResponse,
+    DepositStakeResponse, ExecuteMsg, FreeWindow, InstantiateMsg, NewProperty, OffersResponse,
+    PropertyResponse, QueryMsg, RentOffer, RentQuoteResponse, ReputationResponse, SudoMsg,
+};
+use lease_management_system::state::{DepositStaking, FlatInfo, LeaseTerm};
+use lease_management_system::ContractError;
+
+const OWNER: &str = "owner";
+const LANDLORD1: &str = "landlord1";
+const LANDLORD2: &str = "landlord2";
+const LANDLORD3: &str = "landlord3";
+const RENTEE: &str = "rentee";
+const RENTEE2: &str = "rentee2";
+const VALIDATOR: &str = "validator";
+
+fn mock_app() -> App {
+    AppBuilder::new().build()
+}
+
+fn contract_lease() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        lease_management_system::contract::execute,
+        lease_management_system::contract::instantiate,
+        lease_management_system::contract::query,
+    )
+    .with_reply(lease_management_system::contract::reply)
+    .with_sudo(lease_management_system::contract::sudo);
+    Box::new(contract)
+}
+
+fn contract_group() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn contract_multisig() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw3_flex_multisig::contract::execute,
+        cw3_flex_multisig::contract::instantiate,
+        cw3_flex_multisig::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn member(addr: &str, weight: u64) -> Member {
+    Member {
+        addr: addr.to_string(),
+        weight,
+    }
+}
+
+/// Instantiates the cw4 group of landlords, all with weight 1, managed by the owner
+fn setup_group(app: &mut App) -> Addr {
+    let group_id = app.store_code(contract_group());
+    let members = [LANDLORD1, LANDLORD2, LANDLORD3]
+        .iter()
+        .map(|addr| member(addr, 1))
+        .collect();
+    app.instantiate_contract(
+        group_id,
+        Addr::unchecked(OWNER),
+        &cw4_group::msg::InstantiateMsg {
+            admin: Some(OWNER.to_string()),
+            members,
+        },
+        &[],
+        "landlords",
+        None,
+    )
+    .unwrap()
+}
+
+fn instantiate_lease(app: &mut App, landlord_group: Option<&Addr>) -> Addr {
+    let lease_id = app.store_code(contract_lease());
+    app.instantiate_contract(
+        lease_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg {
+            landlord_group: landlord_group.map(Addr::to_string),
+        },
+        &[],
+        "lease",
+        None,
+    )
+    .unwrap()
+}
+
+/// Instantiates the lease contract governed by a 2 of 3 multisig of landlords.
+/// Returns the addresses of the lease contract and the multisig.
+fn setup_governance(app: &mut App) -> (Addr, Addr) {
+    let group = setup_group(app);
+
+    let multisig_id = app.store_code(contract_multisig());
+    let multisig = app
+        .instantiate_contract(
+            multisig_id,
+            Addr::unchecked(OWNER),
+            &cw3_flex_multisig::msg::InstantiateMsg {
+                group_addr: group.to_string(),
+                required_weight: 2,
+                max_voting_period: Duration::Height(100),
+            },
+            &[],
+            "governance",
+            None,
+        )
+        .unwrap();
+
+    let lease = instantiate_lease(app, None);
+
+    // hand the admin over to the multisig
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        lease.clone(),
+        &ExecuteMsg::UpdateAdmin {
+            admin: Some(multisig.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the group weights are only visible to proposals from the next block on
+    app.update_block(next_block);
+
+    (lease, multisig)
+}
+
+#[test]
+fn config_changes_through_multisig() {
+    let mut app = mock_app();
+    let (lease, multisig) = setup_governance(&mut app);
+
+    let update = ExecuteMsg::UpdateConfig {
+        lease_period: Some(1000),
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: None,
+    };
+
+    // neither the owner nor a single landlord can change the config directly
+    for sender in [OWNER, LANDLORD1] {
+        let err = app
+            .execute_contract(Addr::unchecked(sender), lease.clone(), &update, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+    }
+
+    let proposal = cw3_flex_multisig::msg::ExecuteMsg::Propose {
+        title: "Shorter lease period".to_string(),
+        description: "Collect rent every 1000 seconds".to_string(),
+        msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lease.to_string(),
+            msg: to_binary(&update).unwrap(),
+            funds: vec![],
+        })],
+        earliest: None,
+        latest: None,
+    };
+    app.execute_contract(Addr::unchecked(LANDLORD1), multisig.clone(), &proposal, &[])
+        .unwrap();
+
+    // one vote is not enough
+    let execute = cw3_flex_multisig::msg::ExecuteMsg::Execute { proposal_id: 1 };
+    let err = app
+        .execute_contract(Addr::unchecked(LANDLORD1), multisig.clone(), &execute, &[])
+        .unwrap_err();
+    assert_eq!(
+        cw3_flex_multisig::ContractError::WrongExecuteStatus {},
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(LANDLORD2),
+        multisig.clone(),
+        &cw3_flex_multisig::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    let prop: cw3::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &multisig,
+            &cw3_flex_multisig::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(Status::Passed, prop.status);
+
+    app.execute_contract(Addr::unchecked(LANDLORD3), multisig, &execute, &[])
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&lease, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(1000, config.lease_period);
+}
+
+fn query_property(app: &App, lease: &Addr, id: u64) -> FlatInfo {
+    let res: PropertyResponse = app
+        .wrap()
+        .query_wasm_smart(lease, &QueryMsg::PropertyDetail(id))
+        .unwrap();
+    res.property
+}
+
+fn is_suspended(app: &App, lease: &Addr, id: u64) -> bool {
+    let res: PropertyResponse = app
+        .wrap()
+        .query_wasm_smart(lease, &QueryMsg::PropertyDetail(id))
+        .unwrap();
+    res.suspended
+}
+
+fn update_members(app: &mut App, group: &Addr, remove: Vec<&str>, add: Vec<Member>) {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        group.clone(),
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: remove.into_iter().map(String::from).collect(),
+            add,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn only_landlords_list_properties() {
+    let mut app = mock_app();
+    let group = setup_group(&mut app);
+    let lease = instantiate_lease(&mut app, Some(&group));
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        group.clone(),
+        &cw4_group::msg::ExecuteMsg::AddHook {
+            addr: lease.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(1000, "acudos"))
+        .unwrap();
+
+    let add_property = ExecuteMsg::AddProperty {
+        rent: Uint128::new(200),
+        min_reputation: None,
+        currency: None,
+    };
+    let err = app
+        .execute_contract(Addr::unchecked(RENTEE), lease.clone(), &add_property, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NotALandlord {}, err.downcast().unwrap());
+
+    // property 0 gets leased, property 1 only requested
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &add_property,
+            &[],
+        )
+        .unwrap();
+    }
+    for property_id in [0, 1] {
+        app.execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &ExecuteMsg::RequestForLease {
+                property_id,
+                term: None,
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            &coins(400, "acudos"),
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AcceptLease {
+            property_id: 0,
+            rent_schedule: None,
+            document: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // only the landlord group reports member changes
+    let err = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD2),
+            lease.clone(),
+            &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg::one(cw4::MemberDiff::new(
+                LANDLORD1,
+                Some(1),
+                None,
+            ))),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // landlord1 drops to zero weight, the pending request can no longer be accepted
+    update_members(&mut app, &group, vec![], vec![member(LANDLORD1, 0)]);
+    assert!(is_suspended(&app, &lease, 0));
+    assert_eq!(
+        Some(RENTEE.to_string()),
+        query_property(&app, &lease, 0).rentee
+    );
+    assert!(is_suspended(&app, &lease, 1));
+    let err = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::AcceptLease {
+                property_id: 1,
+                rent_schedule: None,
+                document: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::PropertySuspended {}, err.downcast().unwrap());
+
+    // the rentee withdraws it instead
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &ExecuteMsg::RejectLease { property_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(None, query_property(&app, &lease, 1).rentee);
+    let balance = app.wrap().query_balance(RENTEE, "acudos").unwrap();
+    assert_eq!(Uint128::new(600), balance.amount);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &add_property,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::NotALandlord {}, err.downcast().unwrap());
+    let request = ExecuteMsg::RequestForLease {
+        property_id: 1,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &request,
+            &coins(400, "acudos"),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::PropertySuspended {}, err.downcast().unwrap());
+
+    // the running lease goes on
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &ExecuteMsg::PayRent {
+            property_id: 0,
+            max_rate: None,
+        },
+        &coins(200, "acudos"),
+    )
+    .unwrap();
+
+    // leaving the group and joining again reinstates the listings
+    update_members(&mut app, &group, vec![LANDLORD1], vec![]);
+    update_members(&mut app, &group, vec![], vec![member(LANDLORD1, 2)]);
+    assert!(!is_suspended(&app, &lease, 0));
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &request,
+        &coins(400, "acudos"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &add_property,
+        &[],
+    )
+    .unwrap();
+
+    // only the admin changes the group, and only to a cw4 group
+    let update = ExecuteMsg::UpdateLandlordGroup {
+        landlord_group: Some(RENTEE.to_string()),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked(LANDLORD1), lease.clone(), &update, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = app
+        .execute_contract(Addr::unchecked(OWNER), lease.clone(), &update, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidGroup {
+            addr: RENTEE.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // without a group anyone can list
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        lease.clone(),
+        &ExecuteMsg::UpdateLandlordGroup {
+            landlord_group: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(Addr::unchecked(LANDLORD3), lease, &add_property, &[])
+        .unwrap();
+}
+
+fn set_price(app: &mut App, oracle: &Addr, rate: Decimal) {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        oracle.clone(),
+        &oracle::ExecuteMsg::SetPrice {
+            currency: "usd".to_string(),
+            rate,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn rent_pegged_to_reference_currency() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    let oracle_id = app.store_code(oracle::contract());
+    let oracle = app
+        .instantiate_contract(
+            oracle_id,
+            Addr::unchecked(OWNER),
+            &EmptyMsg {},
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap();
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(10_000, "acudos"))
+        .unwrap();
+
+    // rent of 100 usd cents
+    let add_property = ExecuteMsg::AddProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: Some("usd".to_string()),
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &add_property,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::NoOracle {}, err.downcast().unwrap());
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        lease.clone(),
+        &ExecuteMsg::UpdateOracle {
+            oracle: Some(oracle.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &add_property,
+        &[],
+    )
+    .unwrap();
+
+    set_price(&mut app, &oracle, Decimal::percent(250));
+    let quote: RentQuoteResponse = app
+        .wrap()
+        .query_wasm_smart(&lease, &QueryMsg::RentQuote { property_id: 0 })
+        .unwrap();
+    assert_eq!(Uint128::new(250), quote.amount);
+    assert_eq!(Some(Decimal::percent(250)), quote.rate);
+
+    // the rentee bounds the rate, first rent and deposit are locked at 2.5 acudos per cent
+    let request = |max_rate| ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: Some(max_rate),
+        document: None,
+        offer: None,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &request(Decimal::percent(200)),
+            &coins(1000, "acudos"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SlippageExceeded {
+            rate: Decimal::percent(250),
+            max_rate: Decimal::percent(200)
+        },
+        err.downcast().unwrap()
+    );
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &request(Decimal::percent(300)),
+        &coins(1000, "acudos"),
+    )
+    .unwrap();
+    assert_eq!(Uint128::new(500), query_property(&app, &lease, 0).escrow);
+    assert_eq!(
+        9500,
+        app.wrap()
+            .query_balance(RENTEE, "acudos")
+            .unwrap()
+            .amount
+            .u128()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AcceptLease {
+            property_id: 0,
+            rent_schedule: None,
+            document: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let landlord = |app: &App| {
+        app.wrap()
+            .query_balance(LANDLORD1, "acudos")
+            .unwrap()
+            .amount
+            .u128()
+    };
+    assert_eq!(250, landlord(&app));
+
+    // the next rent is converted at the new rate
+    set_price(&mut app, &oracle, Decimal::percent(300));
+    let pay_rent = |max_rate| ExecuteMsg::PayRent {
+        property_id: 0,
+        max_rate,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &pay_rent(Some(Decimal::percent(250))),
+            &coins(300, "acudos"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SlippageExceeded {
+            rate: Decimal::percent(300),
+            max_rate: Decimal::percent(250)
+        },
+        err.downcast().unwrap()
+    );
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &pay_rent(None),
+            &coins(250, "acudos"),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::LessThanRent {}, err.downcast().unwrap());
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &pay_rent(None),
+        &coins(300, "acudos"),
+    )
+    .unwrap();
+    assert_eq!(550, landlord(&app));
+
+    // a rent too large to convert fails instead of panicking
+    app.init_bank_balance(&Addr::unchecked(RENTEE2), coins(1000, "acudos"))
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperty {
+            rent: Uint128::new(u128::MAX / 2),
+            min_reputation: None,
+            currency: Some("usd".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart::<RentQuoteResponse>(&lease, &QueryMsg::RentQuote { property_id: 1 })
+        .unwrap_err();
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE2),
+            lease,
+            &ExecuteMsg::RequestForLease {
+                property_id: 1,
+                term: None,
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            &coins(1000, "acudos"),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Std(StdError::Overflow { .. })
+    ));
+}
+
+#[test]
+fn bulk_accept_is_atomic() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(10_000, "acudos"))
+        .unwrap();
+
+    let properties = (1..=3)
+        .map(|i| NewProperty {
+            rent: Uint128::new(100 * i),
+            min_reputation: None,
+            currency: None,
+        })
+        .collect();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::AddProperties { properties },
+            &[],
+        )
+        .unwrap();
+    let listed = res
+        .events
+        .iter()
+        .filter(|e| e.ty == "wasm-add_property")
+        .count();
+    assert_eq!(3, listed);
+
+    for (property_id, amount) in [(0, 200), (1, 400)] {
+        app.execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &ExecuteMsg::RequestForLease {
+                property_id,
+                term: None,
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            &coins(amount, "acudos"),
+        )
+        .unwrap();
+    }
+
+    // property 2 was never requested, so none of the leases starts
+    let err = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::BulkAccept {
+                property_ids: vec![0, 1, 2],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::IsNotRented {}, err.downcast().unwrap());
+    assert_eq!(None, query_property(&app, &lease, 0).expires);
+    let landlord = |app: &App| {
+        app.wrap()
+            .query_balance(LANDLORD1, "acudos")
+            .unwrap()
+            .amount
+            .u128()
+    };
+    assert_eq!(0, landlord(&app));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::BulkAccept {
+                property_ids: vec![0, 1],
+            },
+            &[],
+        )
+        .unwrap();
+    let accepted = res
+        .events
+        .iter()
+        .filter(|e| e.ty == "wasm-accept_lease")
+        .count();
+    assert_eq!(2, accepted);
+    assert!(query_property(&app, &lease, 1).expires.is_some());
+    assert_eq!(300, landlord(&app));
+}
+
+#[test]
+fn failed_payouts_become_claimable() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(200, "acudos"))
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperty {
+            rent: Uint128::new(100),
+            min_reputation: None,
+            currency: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &ExecuteMsg::RequestForLease {
+            property_id: 0,
+            term: None,
+            max_rate: None,
+            document: None,
+            offer: None,
+        },
+        &coins(200, "acudos"),
+    )
+    .unwrap();
+
+    // the refund to the rentee fails, eg. because the address is blocked
+    app.init_bank_balance(&lease, vec![]).unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::RejectLease { property_id: 0 },
+            &[],
+        )
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .flat_map(|e| &e.attributes)
+        .any(|a| a.value == "payout_failed"));
+
+    // the property is free again, the refund waits for the rentee
+    assert_eq!(None, query_property(&app, &lease, 0).rentee);
+    let claimable = |app: &App| {
+        let res: ClaimablePayoutResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &lease,
+                &QueryMsg::ClaimablePayout {
+                    address: RENTEE.to_string(),
+                },
+            )
+            .unwrap();
+        res.amount.u128()
+    };
+    assert_eq!(200, claimable(&app));
+
+    // a failing claim keeps the payout claimable
+    let claim = ExecuteMsg::ClaimPayout {};
+    app.execute_contract(Addr::unchecked(RENTEE), lease.clone(), &claim, &[])
+        .unwrap_err();
+    assert_eq!(200, claimable(&app));
+
+    app.init_bank_balance(&lease, coins(200, "acudos")).unwrap();
+    app.execute_contract(Addr::unchecked(RENTEE), lease.clone(), &claim, &[])
+        .unwrap();
+    assert_eq!(0, claimable(&app));
+    let balance = app.wrap().query_balance(RENTEE, "acudos").unwrap();
+    assert_eq!(200, balance.amount.u128());
+
+    let err = app
+        .execute_contract(Addr::unchecked(RENTEE), lease, &claim, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+}
+
+#[test]
+fn sudo_sweeps_expired_leases() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        lease.clone(),
+        &ExecuteMsg::UpdateConfig {
+            lease_period: Some(10),
+            lease_period_seconds: None,
+            max_batch_size: Some(2),
+            pull_rent: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let property = NewProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperties {
+            properties: vec![property.clone(), property],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // an open ended lease and a lease over a single period, both paid for one period
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(400, "acudos"))
+        .unwrap();
+    for (property_id, term) in [(0, None), (1, Some(LeaseTerm::Periods(1)))] {
+        app.execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &ExecuteMsg::RequestForLease {
+                property_id,
+                term,
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            &coins(200, "acudos"),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::AcceptLease {
+                property_id,
+                rent_schedule: None,
+                document: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperty {
+            rent: Uint128::new(100),
+            min_reputation: None,
+            currency: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let expired = |res: &cw_multi_test::AppResponse| {
+        res.events
+            .iter()
+            .filter(|e| e.ty == "wasm-lease_expired")
+            .map(|e| {
+                let outcome = e.attributes.iter().find(|a| a.key == "outcome").unwrap();
+                outcome.value.clone()
+            })
+            .collect::<Vec<_>>()
+    };
+    let sweep = SudoMsg::ProcessExpired { limit: 10 };
+
+    // nothing has expired yet, the sweep moves on by the batch limit
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+
+    // the term is over and fully paid, the open ended lease is still in its grace window
+    app.update_block(|block| block.height += 10);
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), vec!["ended"]);
+    app.update_block(|block| block.height += 1);
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), vec!["defaulted"]);
+    for id in [0, 1] {
+        let property = query_property(&app, &lease, id);
+        assert_eq!(None, property.rentee);
+        assert_eq!(Uint128::zero(), property.escrow);
+    }
+    // both deposits are back with the rentee
+    let balance = app.wrap().query_balance(RENTEE, "acudos").unwrap();
+    assert_eq!(200, balance.amount.u128());
+
+    // the last batch wraps around, settled leases are not swept again
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+    let res = app.sudo(lease, &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+}
+
+#[test]
+fn sudo_sweeps_time_based_terms() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        lease.clone(),
+        &ExecuteMsg::UpdateConfig {
+            lease_period: None,
+            lease_period_seconds: Some(100),
+            max_batch_size: None,
+            pull_rent: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let property = NewProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperties {
+            properties: vec![property.clone(), property],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // two leases until the same time two periods away, only the first one is paid up to it
+    let ends = Expiration::AtTime(app.block_info().time.plus_seconds(200));
+    for rentee in [RENTEE, RENTEE2] {
+        app.init_bank_balance(&Addr::unchecked(rentee), coins(300, "acudos"))
+            .unwrap();
+    }
+    for (property_id, rentee) in [(0, RENTEE), (1, RENTEE2)] {
+        app.execute_contract(
+            Addr::unchecked(rentee),
+            lease.clone(),
+            &ExecuteMsg::RequestForLease {
+                property_id,
+                term: Some(LeaseTerm::Until(ends)),
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            &coins(200, "acudos"),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(LANDLORD1),
+            lease.clone(),
+            &ExecuteMsg::AcceptLease {
+                property_id,
+                rent_schedule: None,
+                document: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &ExecuteMsg::PayRent {
+            property_id: 0,
+            max_rate: None,
+        },
+        &coins(100, "acudos"),
+    )
+    .unwrap();
+    let property = query_property(&app, &lease, 0);
+    assert_eq!(Some(ends), property.expires);
+
+    let expired = |res: &cw_multi_test::AppResponse| {
+        res.events
+            .iter()
+            .filter(|e| e.ty == "wasm-lease_expired")
+            .map(|e| {
+                let outcome = e.attributes.iter().find(|a| a.key == "outcome").unwrap();
+                outcome.value.clone()
+            })
+            .collect::<Vec<_>>()
+    };
+    let sweep = SudoMsg::ProcessExpired { limit: 10 };
+
+    // within the paid period of the second lease nothing is swept, however many blocks pass
+    app.update_block(|block| {
+        block.height += 1000;
+        block.time = block.time.plus_seconds(50);
+    });
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), Vec::<String>::new());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(150));
+    let res = app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(expired(&res), vec!["ended", "defaulted"]);
+    for rentee in [RENTEE, RENTEE2] {
+        let response: ReputationResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &lease,
+                &QueryMsg::Reputation {
+                    address: rentee.to_string(),
+                },
+            )
+            .unwrap();
+        let completed = (rentee == RENTEE) as u64;
+        assert_eq!(completed, response.stats.leases_completed);
+        assert_eq!(1 - completed, response.stats.terminations_for_default);
+    }
+}
+
+fn offer(property_id: u64, rent: u128, expires: Option<Expiration>) -> ExecuteMsg {
+    ExecuteMsg::RequestForLease {
+        property_id,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: Some(RentOffer {
+            rent: Uint128::new(rent),
+            expires,
+        }),
+    }
+}
+
+fn balance(app: &App, addr: &str) -> u128 {
+    app.wrap()
+        .query_balance(addr, "acudos")
+        .unwrap()
+        .amount
+        .u128()
+}
+
+#[test]
+fn offers_and_counter_offers() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    for rentee in [RENTEE, RENTEE2] {
+        app.init_bank_balance(&Addr::unchecked(rentee), coins(1000, "acudos"))
+            .unwrap();
+    }
+    let property = NewProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(LANDLORD1),
+        lease.clone(),
+        &ExecuteMsg::AddProperties {
+            properties: vec![property.clone(), property],
+        },
+        &[],
+    )
+    .unwrap();
+    let execute = |app: &mut App, sender: &str, msg: &ExecuteMsg, funds: u128| {
+        let funds = match funds {
+            0 => vec![],
+            amount => coins(amount, "acudos"),
+        };
+        app.execute_contract(Addr::unchecked(sender), lease.clone(), msg, &funds)
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+    };
+
+    // offers are below the asking rent, twice the offer is locked and the rest refunded
+    let err = execute(&mut app, RENTEE, &offer(0, 100, None), 200).unwrap_err();
+    assert_eq!(ContractError::InvalidOffer {}, err);
+    execute(&mut app, RENTEE, &offer(0, 80, None), 200).unwrap();
+    assert_eq!(840, balance(&app, RENTEE));
+    let err = execute(&mut app, RENTEE, &offer(0, 70, None), 200).unwrap_err();
+    assert_eq!(ContractError::OfferExists {}, err);
+    let expires = Expiration::AtHeight(app.block_info().height + 5);
+    execute(&mut app, RENTEE2, &offer(0, 60, Some(expires)), 120).unwrap();
+
+    let offers: OffersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lease,
+            &QueryMsg::Offers {
+                property_id: 0,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let applicants: Vec<_> = offers.offers.iter().map(|o| o.applicant.as_str()).collect();
+    assert_eq!(vec![RENTEE, RENTEE2], applicants);
+    assert_eq!(Uint128::new(160), offers.offers[0].offer.escrow);
+
+    // only the renter counters, above the offered rent and up to the asking rent
+    let counter = |rent| ExecuteMsg::CounterOffer {
+        property_id: 0,
+        applicant: RENTEE.to_string(),
+        rent: Uint128::new(rent),
+        document: None,
+    };
+    let err = execute(&mut app, RENTEE2, &counter(90), 0).unwrap_err();
+    assert_eq!(ContractError::InvalidRenter {}, err);
+    let err = execute(&mut app, LANDLORD1, &counter(80), 0).unwrap_err();
+    assert_eq!(ContractError::InvalidCounter {}, err);
+    let err = execute(&mut app, LANDLORD1, &counter(110), 0).unwrap_err();
+    assert_eq!(ContractError::InvalidCounter {}, err);
+    execute(&mut app, LANDLORD1, &counter(90), 0).unwrap();
+
+    // the applicant tops the escrow up to twice the counter offer
+    let accept = ExecuteMsg::AcceptCounter {
+        property_id: 0,
+        max_rate: None,
+    };
+    let err = execute(&mut app, RENTEE, &accept, 10).unwrap_err();
+    assert_eq!(ContractError::LessThanRent {}, err);
+    execute(&mut app, RENTEE, &accept, 30).unwrap();
+    let leased = query_property(&app, &lease, 0);
+    assert_eq!(Some(RENTEE.to_string()), leased.rentee);
+    // the lease runs at the countered rent, the listing keeps its asking rent
+    assert_eq!(Uint128::new(100), leased.rent);
+    assert_eq!(Some(Uint128::new(90)), leased.agreed_rent);
+    assert_eq!(Uint128::new(90), leased.escrow);
+    let quote: RentQuoteResponse = app
+        .wrap()
+        .query_wasm_smart(&lease, &QueryMsg::RentQuote { property_id: 0 })
+        .unwrap();
+    assert_eq!(Uint128::new(90), quote.amount);
+    assert_eq!(820, balance(&app, RENTEE));
+    assert_eq!(90, balance(&app, LANDLORD1));
+
+    // an expired offer cannot be accepted, but is refunded when withdrawn
+    app.update_block(|block| block.height += 5);
+    let accept = ExecuteMsg::AcceptOffer {
+        property_id: 0,
+        applicant: RENTEE2.to_string(),
+        document: None,
+    };
+    let err = execute(&mut app, LANDLORD1, &accept, 0).unwrap_err();
+    assert_eq!(ContractError::OfferExpired {}, err);
+    let withdraw = ExecuteMsg::WithdrawOffer { property_id: 0 };
+    execute(&mut app, RENTEE2, &withdraw, 0).unwrap();
+    assert_eq!(1000, balance(&app, RENTEE2));
+    let err = execute(&mut app, RENTEE2, &withdraw, 0).unwrap_err();
+    assert_eq!(ContractError::NoOffer {}, err);
+
+    // rejected offers are refunded, accepted ones start the lease at the offered rent
+    execute(&mut app, RENTEE2, &offer(1, 50, None), 100).unwrap();
+    let reject = ExecuteMsg::RejectOffer {
+        property_id: 1,
+        applicant: RENTEE2.to_string(),
+    };
+    execute(&mut app, LANDLORD1, &reject, 0).unwrap();
+    assert_eq!(1000, balance(&app, RENTEE2));
+    execute(&mut app, RENTEE2, &offer(1, 70, None), 140).unwrap();
+    let accept = ExecuteMsg::AcceptOffer {
+        property_id: 1,
+        applicant: RENTEE2.to_string(),
+        document: None,
+    };
+    execute(&mut app, LANDLORD1, &accept, 0).unwrap();
+    let leased = query_property(&app, &lease, 1);
+    assert_eq!(Some(RENTEE2.to_string()), leased.rentee);
+    assert_eq!(Uint128::new(100), leased.rent);
+    assert_eq!(Some(Uint128::new(70)), leased.agreed_rent);
+    assert!(leased.expires.is_some());
+    assert_eq!(160, balance(&app, LANDLORD1));
+}
+
+#[test]
+fn lease_contract_helper() {
+    let mut app = mock_app();
+    let lease = LeaseContract::new(instantiate_lease(&mut app, None));
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(1000, "acudos"))
+        .unwrap();
+
+    // messages are built by the helper, like another contract would
+    let msg = lease
+        .call(ExecuteMsg::AddProperty {
+            rent: Uint128::new(100),
+            min_reputation: None,
+            currency: None,
+        })
+        .unwrap();
+    app.execute(Addr::unchecked(LANDLORD1), msg).unwrap();
+    let query = app.wrap();
+    assert_eq!(1, lease.total_properties(&query).unwrap());
+    let listed = lease
+        .search_properties(&query, None, None, None, None, None, None)
+        .unwrap();
+    assert_eq!(
+        vec![0],
+        listed.iter().map(|p| p.property_id).collect::<Vec<_>>()
+    );
+
+    let msg = lease
+        .call_with_funds(
+            ExecuteMsg::RequestForLease {
+                property_id: 0,
+                term: None,
+                max_rate: None,
+                document: None,
+                offer: None,
+            },
+            coins(200, "acudos"),
+        )
+        .unwrap();
+    app.execute(Addr::unchecked(RENTEE), msg).unwrap();
+    let msg = lease
+        .call(ExecuteMsg::AcceptLease {
+            property_id: 0,
+            rent_schedule: None,
+            document: None,
+        })
+        .unwrap();
+    app.execute(Addr::unchecked(LANDLORD1), msg).unwrap();
+
+    let query = app.wrap();
+    let property = lease.property(&query, 0).unwrap();
+    assert_eq!(Some(RENTEE.to_string()), property.rentee);
+    let status = lease.lease_status(&query, 0).unwrap();
+    assert_eq!(Some(Uint128::new(100)), status.rent_due);
+    let overview = lease.tenant_overview(&query, RENTEE, None, None).unwrap();
+    assert_eq!(1, overview.leases.len());
+    assert_eq!(Uint128::new(100), overview.paid);
+    assert_eq!(
+        Uint128::zero(),
+        lease.claimable_payout(&query, LANDLORD1).unwrap()
+    );
+    assert!(lease.balance(&query, LANDLORD1).unwrap().is_empty());
+    assert!(!lease.pause_status(&query).unwrap().all);
+}
+
+#[test]
+fn deposits_are_staked() {
+    let mut app = AppBuilder::new()
+        .with_staking(StakeKeeper::new("acudos"))
+        .build();
+    app.add_validator(Validator {
+        address: String::from(VALIDATOR),
+        commission: Decimal::percent(5),
+        max_commission: Decimal::percent(20),
+        max_change_rate: Decimal::percent(1),
+    })
+    .unwrap();
+    let lease = instantiate_lease(&mut app, None);
+    for rentee in [RENTEE, RENTEE2] {
+        app.init_bank_balance(&Addr::unchecked(rentee), coins(1000, "acudos"))
+            .unwrap();
+    }
+    let execute = |app: &mut App, sender: &str, msg: &ExecuteMsg, funds: u128| {
+        let funds = match funds {
+            0 => vec![],
+            amount => coins(amount, "acudos"),
+        };
+        app.execute_contract(Addr::unchecked(sender), lease.clone(), msg, &funds)
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+    };
+    let update_config = ExecuteMsg::UpdateConfig {
+        lease_period: Some(10),
+        lease_period_seconds: None,
+        max_batch_size: None,
+        pull_rent: None,
+    };
+    execute(&mut app, OWNER, &update_config, 0).unwrap();
+    let property = NewProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    let add = ExecuteMsg::AddProperties {
+        properties: vec![property.clone(), property.clone(), property],
+    };
+    execute(&mut app, LANDLORD1, &add, 0).unwrap();
+
+    // only known validators and rentee shares up to 1
+    let update_staking = |validator: &str, rentee_share| ExecuteMsg::UpdateStaking {
+        staking: Some(DepositStaking {
+            validator: validator.to_string(),
+            unbonding_period: Duration::Height(5),
+            rentee_share,
+        }),
+    };
+    let err = execute(&mut app, OWNER, &update_staking("other", Decimal::one()), 0).unwrap_err();
+    assert_eq!(
+        ContractError::UnknownValidator {
+            validator: String::from("other")
+        },
+        err
+    );
+    let staking = update_staking(VALIDATOR, Decimal::percent(150));
+    let err = execute(&mut app, OWNER, &staking, 0).unwrap_err();
+    assert_eq!(ContractError::InvalidRewardShare {}, err);
+    execute(
+        &mut app,
+        OWNER,
+        &update_staking(VALIDATOR, Decimal::percent(50)),
+        0,
+    )
+    .unwrap();
+
+    let request = |property_id| ExecuteMsg::RequestForLease {
+        property_id,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let deposit_stake = |app: &App, property_id| -> DepositStakeResponse {
+        app.wrap()
+            .query_wasm_smart(&lease, &QueryMsg::DepositStake { property_id })
+            .unwrap()
+    };
+
+    // the deposit is delegated on acceptance
+    execute(&mut app, RENTEE, &request(0), 200).unwrap();
+    assert_eq!(None, deposit_stake(&app, 0).validator);
+    let accept = ExecuteMsg::AcceptLease {
+        property_id: 0,
+        rent_schedule: None,
+        document: None,
+    };
+    execute(&mut app, LANDLORD1, &accept, 0).unwrap();
+    assert_eq!(0, balance(&app, lease.as_str()));
+    assert_eq!(100, balance(&app, BONDED_POOL));
+    let stake = deposit_stake(&app, 0);
+    assert_eq!(Some(VALIDATOR.to_string()), stake.validator);
+    assert_eq!(Uint128::new(100), stake.amount);
+
+    // rewards earned before another deposit joins stay with the first one, even though
+    // both deposits of the batch see them before they are withdrawn
+    app.add_staking_rewards(&lease, VALIDATOR, Uint128::new(30))
+        .unwrap();
+    app.update_block(next_block);
+    execute(&mut app, RENTEE2, &request(1), 200).unwrap();
+    execute(&mut app, RENTEE2, &request(2), 200).unwrap();
+    let bulk_accept = ExecuteMsg::BulkAccept {
+        property_ids: vec![1, 2],
+    };
+    execute(&mut app, LANDLORD1, &bulk_accept, 0).unwrap();
+    assert_eq!(30, balance(&app, lease.as_str()));
+    assert_eq!(300, balance(&app, BONDED_POOL));
+
+    app.add_staking_rewards(&lease, VALIDATOR, Uint128::new(60))
+        .unwrap();
+    app.update_block(next_block);
+    assert_eq!(Uint128::new(50), deposit_stake(&app, 0).rewards);
+    assert_eq!(Uint128::new(20), deposit_stake(&app, 1).rewards);
+
+    // closing the leases splits the rewards and leaves the deposits unbonding
+    app.update_block(|block| block.height += 10);
+    let sweep = SudoMsg::ProcessExpired { limit: 10 };
+    app.sudo(lease.clone(), &sweep).unwrap();
+    assert_eq!(0, balance(&app, BONDED_POOL));
+    assert_eq!(825, balance(&app, RENTEE));
+    assert_eq!(620, balance(&app, RENTEE2));
+    assert_eq!(345, balance(&app, LANDLORD1));
+    assert_eq!(300, balance(&app, lease.as_str()));
+    assert_eq!(None, deposit_stake(&app, 0).validator);
+
+    let claims: ClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lease,
+            &QueryMsg::Claims {
+                address: RENTEE.to_string(),
+            },
+        )
+        .unwrap();
+    let release_at = Expiration::AtHeight(app.block_info().height + 5);
+    assert_eq!(vec![Claim::new(100, release_at)], claims.claims);
+
+    let claim = ExecuteMsg::ClaimUnbonded {};
+    let err = execute(&mut app, RENTEE, &claim, 0).unwrap_err();
+    assert_eq!(ContractError::NothingUnbonded {}, err);
+    app.update_block(|block| block.height += 5);
+    execute(&mut app, RENTEE, &claim, 0).unwrap();
+    execute(&mut app, RENTEE2, &claim, 0).unwrap();
+    assert_eq!(925, balance(&app, RENTEE));
+    assert_eq!(820, balance(&app, RENTEE2));
+    assert_eq!(0, balance(&app, lease.as_str()));
+}
+
+#[test]
+fn bookings_calendar() {
+    let mut app = mock_app();
+    let lease = instantiate_lease(&mut app, None);
+    for rentee in [RENTEE, RENTEE2] {
+        app.init_bank_balance(&Addr::unchecked(rentee), coins(1000, "acudos"))
+            .unwrap();
+    }
+    let execute = |app: &mut App, sender: &str, msg: &ExecuteMsg, funds: u128| {
+        let funds = match funds {
+            0 => vec![],
+            amount => coins(amount, "acudos"),
+        };
+        app.execute_contract(Addr::unchecked(sender), lease.clone(), msg, &funds)
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+    };
+    let add = ExecuteMsg::AddProperty {
+        rent: Uint128::new(100),
+        min_reputation: None,
+        currency: None,
+    };
+    execute(&mut app, LANDLORD1, &add, 0).unwrap();
+
+    const DAY: u64 = 24 * 60 * 60;
+    let now = app.block_info().time;
+    let at = |days: f64| Expiration::AtTime(now.plus_seconds((days * DAY as f64) as u64));
+    let book = |start, end| ExecuteMsg::BookProperty {
+        property_id: 0,
+        start,
+        end,
+    };
+    let availability = |app: &App, until, limit| -> Vec<FreeWindow> {
+        let res: AvailabilityResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &lease,
+                &QueryMsg::Availability {
+                    property_id: 0,
+                    from: None,
+                    until,
+                    limit,
+                },
+            )
+            .unwrap();
+        res.windows
+    };
+    let window = |start, end| FreeWindow { start, end };
+
+    // bookings are taken once the renter sets a daily rate
+    let err = execute(&mut app, RENTEE, &book(at(1.0), at(3.0)), 20).unwrap_err();
+    assert_eq!(ContractError::BookingsClosed {}, err);
+    let rate = ExecuteMsg::SetDailyRate {
+        property_id: 0,
+        daily_rate: Some(Uint128::new(10)),
+    };
+    execute(&mut app, LANDLORD1, &rate, 0).unwrap();
+
+    // every started day is paid, the excess is refunded
+    execute(&mut app, RENTEE, &book(at(1.0), at(3.0)), 25).unwrap();
+    assert_eq!(980, balance(&app, RENTEE));
+    let err = execute(&mut app, RENTEE2, &book(at(2.0), at(4.0)), 20).unwrap_err();
+    assert_eq!(ContractError::BookingOverlap {}, err);
+    let err = execute(&mut app, RENTEE2, &book(at(3.0), at(4.5)), 10).unwrap_err();
+    assert_eq!(ContractError::LessThanRent {}, err);
+    execute(&mut app, RENTEE2, &book(at(3.0), at(4.5)), 20).unwrap();
+
+    // stays are given in time and start in the future
+    let in_blocks = book(Expiration::AtHeight(100_000), at(6.0));
+    let err = execute(&mut app, RENTEE2, &in_blocks, 20).unwrap_err();
+    assert_eq!(ContractError::InvalidBooking {}, err);
+    let past = book(Expiration::AtTime(now.minus_seconds(DAY)), at(6.0));
+    let err = execute(&mut app, RENTEE2, &past, 70).unwrap_err();
+    assert_eq!(ContractError::InvalidBooking {}, err);
+
+    // a lease cannot start while stays are booked
+    let request = ExecuteMsg::RequestForLease {
+        property_id: 0,
+        term: None,
+        max_rate: None,
+        document: None,
+        offer: None,
+    };
+    let err = execute(&mut app, RENTEE2, &request, 200).unwrap_err();
+    assert_eq!(ContractError::PropertyBooked {}, err);
+
+    let bookings: BookingsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lease,
+            &QueryMsg::Bookings {
+                property_id: 0,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let guests: Vec<_> = bookings.bookings.iter().map(|b| b.guest.as_str()).collect();
+    assert_eq!(vec![RENTEE, RENTEE2], guests);
+    assert_eq!(Uint128::new(20), bookings.bookings[1].escrow);
+    assert_eq!(
+        vec![
+            window(Expiration::AtTime(now), at(1.0)),
+            window(at(4.5), at(6.0))
+        ],
+        availability(&app, at(6.0), None)
+    );
+    assert_eq!(
+        vec![window(Expiration::AtTime(now), at(1.0))],
+        availability(&app, at(6.0), Some(1))
+    );
+
+    // cancelled before the stay begins, the booking is refunded and its interval freed
+    let cancel = ExecuteMsg::CancelBooking {
+        property_id: 0,
+        start: at(3.0),
+    };
+    let err = execute(&mut app, LANDLORD2, &cancel, 0).unwrap_err();
+    assert_eq!(ContractError::InvalidRenter {}, err);
+    execute(&mut app, RENTEE2, &cancel, 0).unwrap();
+    assert_eq!(1000, balance(&app, RENTEE2));
+    assert_eq!(
+        vec![
+            window(Expiration::AtTime(now), at(1.0)),
+            window(at(3.0), at(6.0))
+        ],
+        availability(&app, at(6.0), None)
+    );
+
+    // a running stay is neither cancelled nor closed, and not available
+    app.update_block(|block| block.time = block.time.plus_seconds(DAY * 3 / 2));
+    let cancel = ExecuteMsg::CancelBooking {
+        property_id: 0,
+        start: at(1.0),
+    };
+    let err = execute(&mut app, RENTEE, &cancel, 0).unwrap_err();
+    assert_eq!(ContractError::BookingStarted {}, err);
+    let close = ExecuteMsg::CloseBooking {
+        property_id: 0,
+        start: at(1.0),
+    };
+    let err = execute(&mut app, RENTEE2, &close, 0).unwrap_err();
+    assert_eq!(ContractError::BookingNotOver {}, err);
+    assert_eq!(
+        vec![window(at(3.0), at(6.0))],
+        availability(&app, at(6.0), None)
+    );
+
+    // once the stay is over its escrow goes to the renter and leases can start again
+    app.update_block(|block| block.time = block.time.plus_seconds(DAY * 3 / 2));
+    execute(&mut app, RENTEE2, &close, 0).unwrap();
+    assert_eq!(20, balance(&app, LANDLORD1));
+    let err = execute(&mut app, RENTEE2, &close, 0).unwrap_err();
+    assert_eq!(ContractError::NoBooking {}, err);
+    execute(&mut app, RENTEE2, &request, 200).unwrap();
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "../examples/schema.rs"]
+mod schema;
+
+fn read_schemas(dir: &Path) -> BTreeMap<String, String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read_to_string(path).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn schema_is_up_to_date() {
+    let out_dir = std::env::temp_dir().join(format!("lease-schema-{}", std::process::id()));
+    fs::create_dir_all(&out_dir).unwrap();
+    schema::export_schemas(&out_dir);
+    let generated = read_schemas(&out_dir);
+    fs::remove_dir_all(&out_dir).unwrap();
+
+    let checked_in = read_schemas(&Path::new(env!("CARGO_MANIFEST_DIR")).join("schema"));
+    assert!(
+        generated == checked_in,
+        "schema directory is stale, regenerate it with `cargo schema`"
+    );
+}
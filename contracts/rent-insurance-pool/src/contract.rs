@@ -0,0 +1,230 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    Uint128,
+};
+use cw0::must_pay;
+use cw2::set_contract_version;
+use cw_storage_plus::U64Key;
+use lease::{InsurancePremiumMsg, LeaseChangedHookMsg, LeaseEvent};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg},
+    state::{Config, Coverage, InsuredDefault, CLAIMS, CONFIG, COVERAGE, DEFAULTS, RESERVED},
+    ContractError,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:rent-insurance-pool";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let config = Config {
+        lease_contract: deps.api.addr_validate(&msg.lease_contract)?,
+        denom: msg.denom,
+        payout_cap: msg.payout_cap,
+        claim_window: msg.claim_window,
+        claim_delay: msg.claim_delay,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    RESERVED.save(deps.storage, &Uint128::zero())?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::InsurancePremium(msg) => execute_premium(deps, info, msg),
+        ExecuteMsg::LeaseChangedHook(msg) => execute_lease_changed(deps, env, info, msg),
+        ExecuteMsg::FileClaim { property_id } => execute_file_claim(deps, env, info, property_id),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+    }
+}
+
+// only the configured lease contract forwards premiums and reports lease transitions
+fn assert_lease_contract(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    if *sender != config.lease_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_premium(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: InsurancePremiumMsg,
+) -> Result<Response, ContractError> {
+    // Premiums add up per lease, a lease is insured once any premium was paid on it.
+    // The payout is based on the rent of the last period paid.
+    let config = CONFIG.load(deps.storage)?;
+    assert_lease_contract(&config, &info.sender)?;
+    let premium = must_pay(&info, &config.denom)?;
+
+    let rentee = deps.api.addr_validate(&msg.rentee)?;
+    COVERAGE.update(
+        deps.storage,
+        (msg.property_id.into(), &rentee),
+        |coverage| -> StdResult<_> {
+            let coverage = coverage.unwrap_or_default();
+            Ok(Coverage {
+                premiums: coverage.premiums + premium,
+                rent: msg.rent,
+            })
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "premium")
+        .add_attribute("property_id", msg.property_id.to_string())
+        .add_attribute("rentee", rentee)
+        .add_attribute("amount", premium))
+}
+
+pub fn execute_lease_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: LeaseChangedHookMsg,
+) -> Result<Response, ContractError> {
+    // A default on an insured lease opens the claim window for the renter. A lease
+    // ending normally drops its coverage.
+    let config = CONFIG.load(deps.storage)?;
+    assert_lease_contract(&config, &info.sender)?;
+
+    let rentee = deps.api.addr_validate(&msg.rentee)?;
+    let key = (U64Key::from(msg.property_id), &rentee);
+    let mut res = Response::new().add_attribute("action", "lease_changed");
+    match msg.event {
+        LeaseEvent::Defaulted => {
+            if let Some(coverage) = COVERAGE.may_load(deps.storage, key.clone())? {
+                COVERAGE.remove(deps.storage, key);
+                let insured = InsuredDefault {
+                    renter: deps.api.addr_validate(&msg.renter)?,
+                    rentee,
+                    rent: coverage.rent,
+                    file_until: config.claim_window.after(&env.block),
+                };
+                DEFAULTS.save(deps.storage, msg.property_id.into(), &insured)?;
+                res = res.add_attribute("insured_default", msg.property_id.to_string());
+            }
+        }
+        LeaseEvent::Ended => COVERAGE.remove(deps.storage, key),
+        LeaseEvent::Started | LeaseEvent::Renewed => {}
+    }
+    Ok(res)
+}
+
+pub fn execute_file_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    property_id: u64,
+) -> Result<Response, ContractError> {
+    // The payout covers one period of rent, capped by the config and by the funds not
+    // already set aside for other claims.
+    let config = CONFIG.load(deps.storage)?;
+    let insured = DEFAULTS
+        .may_load(deps.storage, property_id.into())?
+        .ok_or(ContractError::NoDefault {})?;
+    if insured.renter != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if insured.file_until.is_expired(&env.block) {
+        return Err(ContractError::ClaimWindowClosed {});
+    }
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.denom)?
+        .amount;
+    let reserved = RESERVED.load(deps.storage)?;
+    let available = balance.checked_sub(reserved).unwrap_or_default();
+    let payout = insured.rent.min(config.payout_cap).min(available);
+    if payout.is_zero() {
+        return Err(ContractError::PoolExhausted {});
+    }
+
+    DEFAULTS.remove(deps.storage, property_id.into());
+    RESERVED.save(deps.storage, &(reserved + payout))?;
+    let release_at = config.claim_delay.after(&env.block);
+    CLAIMS.create_claim(deps.storage, &info.sender, payout, release_at)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "file_claim")
+        .add_attribute("property_id", property_id.to_string())
+        .add_attribute("renter", info.sender)
+        .add_attribute("payout", payout))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    RESERVED.update(deps.storage, |reserved| -> StdResult<_> {
+        Ok(reserved.checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("renter", &info.sender)
+        .add_attribute("amount", amount)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.into(),
+            amount: coins(amount.u128(), config.denom),
+        }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Pool {} => to_binary(&query_pool(deps, env)?),
+        QueryMsg::Coverage {
+            property_id,
+            rentee,
+        } => to_binary(&query_coverage(deps, property_id, rentee)?),
+        QueryMsg::Default { property_id } => {
+            to_binary(&DEFAULTS.may_load(deps.storage, property_id.into())?)
+        }
+        QueryMsg::Claims { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_binary(&CLAIMS.query_claims(deps, &address)?)
+        }
+    }
+}
+
+pub fn query_pool(deps: Deps, env: Env) -> StdResult<PoolResponse> {
+    let denom = CONFIG.load(deps.storage)?.denom;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, denom)?
+        .amount;
+    let reserved = RESERVED.load(deps.storage)?;
+    Ok(PoolResponse { balance, reserved })
+}
+
+pub fn query_coverage(deps: Deps, property_id: u64, rentee: String) -> StdResult<Coverage> {
+    let rentee = deps.api.addr_validate(&rentee)?;
+    Ok(COVERAGE
+        .may_load(deps.storage, (property_id.into(), &rentee))?
+        .unwrap_or_default())
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::StdError;
+use cw0::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("No insured default to claim for this property")]
+    NoDefault {},
+
+    #[error("Claim window for this default has closed")]
+    ClaimWindowClosed {},
+
+    #[error("Pool has no funds left to pay out")]
+    PoolExhausted {},
+
+    #[error("No claim has matured yet")]
+    NothingToClaim {},
+}
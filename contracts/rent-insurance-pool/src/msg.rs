@@ -0,0 +1,56 @@
+use cosmwasm_std::Uint128;
+use cw0::Duration;
+use lease::{InsurancePremiumMsg, LeaseChangedHookMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// Lease contract forwarding the premiums and reporting defaults
+    pub lease_contract: String,
+    /// Native denom premiums are paid and claims are paid out in
+    pub denom: String,
+    /// Maximum payout for a single default
+    pub payout_cap: Uint128,
+    /// How long after a default the renter can file a claim
+    pub claim_window: Duration,
+    /// How long a filed claim waits before it can be paid out
+    pub claim_delay: Duration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Premium forwarded by the lease contract on PayRent
+    InsurancePremium(InsurancePremiumMsg),
+    /// Lease lifecycle transitions, the pool must be added as hook of the lease contract
+    LeaseChangedHook(LeaseChangedHookMsg),
+    /// The renter files a claim for an insured default on the property. The payout
+    /// becomes claimable after the claim delay.
+    FileClaim { property_id: u64 },
+    /// Pays out all matured claims of the sender
+    Claim {},
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the Config
+    Config {},
+    /// Returns PoolResponse with the funds of the pool
+    Pool {},
+    /// Returns the Coverage bought on the current lease
+    Coverage { property_id: u64, rentee: String },
+    /// Returns the insured default waiting for a claim, if any
+    Default { property_id: u64 },
+    /// Returns cw_controllers::ClaimsResponse with the filed claims of the address
+    Claims { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PoolResponse {
+    pub balance: Uint128,
+    /// Part of the balance set aside for filed claims
+    pub reserved: Uint128,
+}
@@ -0,0 +1,45 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw0::{Duration, Expiration};
+use cw_controllers::Claims;
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Config {
+    pub lease_contract: Addr,
+    pub denom: String,
+    pub payout_cap: Uint128,
+    pub claim_window: Duration,
+    pub claim_delay: Duration,
+}
+
+/// Insurance of the current lease on a property
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct Coverage {
+    /// Sum of the premiums paid
+    pub premiums: Uint128,
+    /// acudos rent of the last period paid, the payout covers at most one period of it
+    pub rent: Uint128,
+}
+
+/// Default of an insured lease, waiting for the renter to file a claim
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InsuredDefault {
+    pub renter: Addr,
+    pub rentee: Addr,
+    /// Rent of the last period paid, the payout covers at most one period of it
+    pub rent: Uint128,
+    /// Claims can be filed until this expires
+    pub file_until: Expiration,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Coverage bought by the rentee of the current lease on the property
+pub const COVERAGE: Map<(U64Key, &Addr), Coverage> = Map::new("coverage");
+/// Latest insured default per property
+pub const DEFAULTS: Map<U64Key, InsuredDefault> = Map::new("defaults");
+/// Sum of filed claims that were not paid out yet
+pub const RESERVED: Item<Uint128> = Item::new("reserved");
+/// Filed claims of the renters, released after the claim delay
+pub const CLAIMS: Claims = Claims::new("claims");
@@ -0,0 +1,341 @@
+use cosmwasm_std::{coins, Addr, Decimal, Empty, Uint128};
+use cw0::Duration;
+use cw_controllers::ClaimsResponse;
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+use lease::{InsurancePremiumMsg, TotalPropertiesResponse};
+use lease_management_system::msg::{
+    ExecuteMsg as LeaseExecuteMsg, InstantiateMsg as LeaseInstantiateMsg, InsuranceInfo,
+};
+use lease_management_system::ContractError as LeaseError;
+use rent_insurance_pool::msg::{ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg};
+use rent_insurance_pool::state::{Coverage, InsuredDefault};
+use rent_insurance_pool::ContractError;
+
+const OWNER: &str = "owner";
+const RENTER: &str = "renter";
+const RENTEE: &str = "rentee";
+const DENOM: &str = "acudos";
+const LEASE_PERIOD: u64 = 411428;
+
+fn mock_app() -> App {
+    AppBuilder::new().build()
+}
+
+fn contract_lease() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        lease_management_system::contract::execute,
+        lease_management_system::contract::instantiate,
+        lease_management_system::contract::query,
+    )
+    .with_reply(lease_management_system::contract::reply);
+    Box::new(contract)
+}
+
+fn contract_pool() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        rent_insurance_pool::contract::execute,
+        rent_insurance_pool::contract::instantiate,
+        rent_insurance_pool::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates the lease contract with a 5% premium going to a pool holding 1000acudos,
+/// paying out at most 150acudos per default. Claims can be filed for 100 blocks and are
+/// paid out 50 blocks later.
+fn setup(app: &mut App) -> (Addr, Addr) {
+    let lease_id = app.store_code(contract_lease());
+    let lease = app
+        .instantiate_contract(
+            lease_id,
+            Addr::unchecked(OWNER),
+            &LeaseInstantiateMsg {
+                landlord_group: None,
+            },
+            &[],
+            "lease",
+            None,
+        )
+        .unwrap();
+
+    let pool_id = app.store_code(contract_pool());
+    let pool = app
+        .instantiate_contract(
+            pool_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                lease_contract: lease.to_string(),
+                denom: DENOM.to_string(),
+                payout_cap: Uint128::new(150),
+                claim_window: Duration::Height(100),
+                claim_delay: Duration::Height(50),
+            },
+            &[],
+            "insurance",
+            None,
+        )
+        .unwrap();
+
+    for msg in [
+        LeaseExecuteMsg::AddHook {
+            addr: pool.to_string(),
+        },
+        LeaseExecuteMsg::UpdateInsurance {
+            insurance: Some(InsuranceInfo {
+                pool: pool.to_string(),
+                premium: Decimal::percent(5),
+            }),
+        },
+    ] {
+        app.execute_contract(Addr::unchecked(OWNER), lease.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    app.init_bank_balance(&pool, coins(1000, DENOM)).unwrap();
+    app.init_bank_balance(&Addr::unchecked(RENTEE), coins(10_000, DENOM))
+        .unwrap();
+
+    (lease, pool)
+}
+
+/// Lists a property with the given rent and leases it to the rentee
+fn start_lease(app: &mut App, lease: &Addr, rent: u128) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(RENTER),
+        lease.clone(),
+        &LeaseExecuteMsg::AddProperty {
+            rent: Uint128::new(rent),
+            min_reputation: None,
+            currency: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let property_id: u64 = app
+        .wrap()
+        .query_wasm_smart(
+            lease,
+            &lease_management_system::msg::QueryMsg::GetTotalProperties,
+        )
+        .map(|res: TotalPropertiesResponse| res.total - 1)
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &LeaseExecuteMsg::RequestForLease {
+            property_id,
+            term: None,
+            max_rate: None,
+            document: None,
+            offer: None,
+        },
+        &coins(rent * 2, DENOM),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(RENTER),
+        lease.clone(),
+        &LeaseExecuteMsg::AcceptLease {
+            property_id,
+            rent_schedule: None,
+            document: None,
+        },
+        &[],
+    )
+    .unwrap();
+    property_id
+}
+
+fn pay_rent(app: &mut App, lease: &Addr, property_id: u64, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(RENTEE),
+        lease.clone(),
+        &LeaseExecuteMsg::PayRent {
+            property_id,
+            max_rate: None,
+        },
+        &coins(amount, DENOM),
+    )
+    .unwrap();
+}
+
+/// Lets the paid periods run out and terminates the lease for default
+fn default(app: &mut App, lease: &Addr, property_id: u64) {
+    app.update_block(|block| block.height += 3 * LEASE_PERIOD);
+    app.execute_contract(
+        Addr::unchecked(RENTER),
+        lease.clone(),
+        &LeaseExecuteMsg::TerminateLease { property_id },
+        &[],
+    )
+    .unwrap();
+}
+
+fn file_claim(app: &mut App, pool: &Addr, sender: &str, property_id: u64) -> ContractError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        pool.clone(),
+        &ExecuteMsg::FileClaim { property_id },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
+fn balance(app: &App, addr: &str) -> u128 {
+    app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+#[test]
+fn premiums_are_forwarded_on_pay_rent() {
+    let mut app = mock_app();
+    let (lease, pool) = setup(&mut app);
+    let property_id = start_lease(&mut app, &lease, 200);
+
+    // the rent alone is not enough anymore
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            lease.clone(),
+            &LeaseExecuteMsg::PayRent {
+                property_id,
+                max_rate: None,
+            },
+            &coins(200, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(LeaseError::LessThanRent {}, err.downcast().unwrap());
+
+    // one period with a 10acudos premium, the excess is refunded
+    let renter_before = balance(&app, RENTER);
+    pay_rent(&mut app, &lease, property_id, 215);
+    assert_eq!(renter_before + 200, balance(&app, RENTER));
+    // two periods at once
+    pay_rent(&mut app, &lease, property_id, 420);
+    assert_eq!(renter_before + 600, balance(&app, RENTER));
+
+    let coverage: Coverage = app
+        .wrap()
+        .query_wasm_smart(
+            &pool,
+            &QueryMsg::Coverage {
+                property_id,
+                rentee: RENTEE.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::new(30), coverage.premiums);
+    assert_eq!(Uint128::new(200), coverage.rent);
+    assert_eq!(1030, balance(&app, pool.as_str()));
+
+    // only the lease contract forwards premiums
+    let err = app
+        .execute_contract(
+            Addr::unchecked(RENTEE),
+            pool,
+            &ExecuteMsg::InsurancePremium(InsurancePremiumMsg::new(
+                property_id,
+                RENTEE,
+                Uint128::new(200),
+            )),
+            &coins(10, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn renter_claims_after_default() {
+    let mut app = mock_app();
+    let (lease, pool) = setup(&mut app);
+    let property_id = start_lease(&mut app, &lease, 200);
+    pay_rent(&mut app, &lease, property_id, 210);
+    default(&mut app, &lease, property_id);
+
+    let insured: Option<InsuredDefault> = app
+        .wrap()
+        .query_wasm_smart(&pool, &QueryMsg::Default { property_id })
+        .unwrap();
+    assert_eq!(Uint128::new(200), insured.unwrap().rent);
+
+    // only the renter files the claim
+    assert_eq!(
+        ContractError::Unauthorized {},
+        file_claim(&mut app, &pool, RENTEE, property_id)
+    );
+
+    // payout is capped at 150 and set aside
+    app.execute_contract(
+        Addr::unchecked(RENTER),
+        pool.clone(),
+        &ExecuteMsg::FileClaim { property_id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        ContractError::NoDefault {},
+        file_claim(&mut app, &pool, RENTER, property_id)
+    );
+    let funds: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pool, &QueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(Uint128::new(1010), funds.balance);
+    assert_eq!(Uint128::new(150), funds.reserved);
+    let claims: ClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pool,
+            &QueryMsg::Claims {
+                address: RENTER.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(1, claims.claims.len());
+
+    // the payout is released after the claim delay
+    let claim = ExecuteMsg::Claim {};
+    let err = app
+        .execute_contract(Addr::unchecked(RENTER), pool.clone(), &claim, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+
+    app.update_block(|block| block.height += 50);
+    let renter_before = balance(&app, RENTER);
+    app.execute_contract(Addr::unchecked(RENTER), pool.clone(), &claim, &[])
+        .unwrap();
+    assert_eq!(renter_before + 150, balance(&app, RENTER));
+    let funds: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pool, &QueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(Uint128::new(860), funds.balance);
+    assert_eq!(Uint128::zero(), funds.reserved);
+}
+
+#[test]
+fn only_insured_defaults_in_window_are_paid() {
+    let mut app = mock_app();
+    let (lease, pool) = setup(&mut app);
+
+    // no premium was ever paid on this lease
+    let uninsured = start_lease(&mut app, &lease, 200);
+    default(&mut app, &lease, uninsured);
+    assert_eq!(
+        ContractError::NoDefault {},
+        file_claim(&mut app, &pool, RENTER, uninsured)
+    );
+
+    // the claim window closes 100 blocks after the default
+    let insured = start_lease(&mut app, &lease, 200);
+    pay_rent(&mut app, &lease, insured, 210);
+    default(&mut app, &lease, insured);
+    app.update_block(|block| block.height += 101);
+    assert_eq!(
+        ContractError::ClaimWindowClosed {},
+        file_claim(&mut app, &pool, RENTER, insured)
+    );
+}
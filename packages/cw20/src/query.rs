@@ -26,6 +26,7 @@ pub enum Cw20QueryMsg {
     /// Only with "marketing" extension
     /// Returns more metadata on the contract to display in the client:
     /// - description, logo, project url, etc.
+    ///
     /// Return type: MarketingInfoResponse.
     MarketingInfo {},
     /// Only with "marketing" extension
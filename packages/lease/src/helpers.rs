@@ -0,0 +1,274 @@
+use cw0::Expiration;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+
+use crate::{
+    ApplicantOffer, AvailabilityResponse, BalanceResponse, Booking, BookingsResponse,
+    ClaimablePayoutResponse, ConfigResponse, DepositStakeResponse, ExecuteMsg, FlatInfo,
+    FreeWindow, LandlordOverviewResponse, LeaseStatusResponse, ListedProperty, OffersResponse,
+    PauseStatusResponse, PermissionsResponse, PropertyAtHeightResponse, PropertyResponse, QueryMsg,
+    RentQuoteResponse, ReputationResponse, SearchPropertiesResponse, TenantOverviewResponse,
+    TotalPropertiesResponse,
+};
+
+/// LeaseContract is a wrapper around Addr that provides a lot of helpers
+/// for working with the lease management contract from other contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaseContract(pub Addr);
+
+impl LeaseContract {
+    pub fn new(addr: Addr) -> Self {
+        LeaseContract(addr)
+    }
+
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    /// Like call, for the messages that lock or pay funds, eg. RequestForLease and PayRent
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = to_binary(&msg.into())?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds,
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        req: QueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_binary(&req)?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    /*** queries ***/
+
+    pub fn property(&self, querier: &QuerierWrapper, property_id: u64) -> StdResult<FlatInfo> {
+        let res: PropertyResponse = self.query(querier, QueryMsg::PropertyDetail(property_id))?;
+        Ok(res.property)
+    }
+
+    /// Return the property as it was at the beginning of the given block,
+    /// None if it was not listed yet
+    pub fn property_at_height(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+        height: u64,
+    ) -> StdResult<Option<FlatInfo>> {
+        let req = QueryMsg::PropertyAtHeight {
+            property_id,
+            height,
+        };
+        let res: PropertyAtHeightResponse = self.query(querier, req)?;
+        Ok(res.property)
+    }
+
+    pub fn total_properties(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        let res: TotalPropertiesResponse = self.query(querier, QueryMsg::GetTotalProperties)?;
+        Ok(res.total)
+    }
+
+    pub fn lease_status(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+    ) -> StdResult<LeaseStatusResponse> {
+        self.query(querier, QueryMsg::LeaseStatus { property_id })
+    }
+
+    pub fn rent_quote(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+    ) -> StdResult<RentQuoteResponse> {
+        self.query(querier, QueryMsg::RentQuote { property_id })
+    }
+
+    pub fn reputation<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<ReputationResponse> {
+        let address = address.into();
+        self.query(querier, QueryMsg::Reputation { address })
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<ConfigResponse> {
+        self.query(querier, QueryMsg::Config {})
+    }
+
+    pub fn pause_status(&self, querier: &QuerierWrapper) -> StdResult<PauseStatusResponse> {
+        self.query(querier, QueryMsg::PauseStatus {})
+    }
+
+    pub fn permissions<T: Into<String>, U: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        renter: T,
+        manager: U,
+    ) -> StdResult<PermissionsResponse> {
+        let req = QueryMsg::Permissions {
+            renter: renter.into(),
+            manager: manager.into(),
+        };
+        self.query(querier, req)
+    }
+
+    /// Get the acudos of failed payouts the address can claim
+    pub fn claimable_payout<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<Uint128> {
+        let address = address.into();
+        let res: ClaimablePayoutResponse =
+            self.query(querier, QueryMsg::ClaimablePayout { address })?;
+        Ok(res.amount)
+    }
+
+    /// Get the rent accrued for a renter
+    pub fn balance<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<Vec<Coin>> {
+        let address = address.into();
+        let res: BalanceResponse = self.query(querier, QueryMsg::Balance { address })?;
+        Ok(res.balance)
+    }
+
+    pub fn tenant_overview<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TenantOverviewResponse> {
+        let req = QueryMsg::TenantOverview {
+            address: address.into(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn landlord_overview<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<LandlordOverviewResponse> {
+        let req = QueryMsg::LandlordOverview {
+            address: address.into(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    /// Find the properties open for requests within the rent range, see
+    /// QueryMsg::SearchProperties
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_properties(
+        &self,
+        querier: &QuerierWrapper,
+        min_rent: Option<Uint128>,
+        max_rent: Option<Uint128>,
+        denom: Option<String>,
+        renter: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ListedProperty>> {
+        let req = QueryMsg::SearchProperties {
+            min_rent,
+            max_rent,
+            denom,
+            renter,
+            start_after,
+            limit,
+        };
+        let res: SearchPropertiesResponse = self.query(querier, req)?;
+        Ok(res.properties)
+    }
+
+    pub fn deposit_stake(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+    ) -> StdResult<DepositStakeResponse> {
+        self.query(querier, QueryMsg::DepositStake { property_id })
+    }
+
+    pub fn offers(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ApplicantOffer>> {
+        let req = QueryMsg::Offers {
+            property_id,
+            start_after,
+            limit,
+        };
+        let res: OffersResponse = self.query(querier, req)?;
+        Ok(res.offers)
+    }
+
+    pub fn bookings(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+        start_after: Option<Expiration>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Booking>> {
+        let req = QueryMsg::Bookings {
+            property_id,
+            start_after,
+            limit,
+        };
+        let res: BookingsResponse = self.query(querier, req)?;
+        Ok(res.bookings)
+    }
+
+    /// Find the intervals a stay can be booked in, see QueryMsg::Availability
+    pub fn availability(
+        &self,
+        querier: &QuerierWrapper,
+        property_id: u64,
+        from: Option<Expiration>,
+        until: Expiration,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<FreeWindow>> {
+        let req = QueryMsg::Availability {
+            property_id,
+            from,
+            until,
+            limit,
+        };
+        let res: AvailabilityResponse = self.query(querier, req)?;
+        Ok(res.windows)
+    }
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+
+/// Lifecycle transitions of a lease that are reported to the hooks
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseEvent {
+    /// The renter accepted the rentee
+    Started,
+    /// Both parties agreed on extending a fixed term lease
+    Renewed,
+    /// The renter terminated the lease after the rentee defaulted on rent
+    Defaulted,
+    /// A fixed term lease reached its end and was closed
+    Ended,
+}
+
+/// LeaseChangedHookMsg should be de/serialized under `LeaseChangedHook()` variant in a ExecuteMsg.
+/// This describes a single transition of the lease on the given property.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct LeaseChangedHookMsg {
+    pub property_id: u64,
+    pub renter: String,
+    pub rentee: String,
+    pub event: LeaseEvent,
+}
+
+impl LeaseChangedHookMsg {
+    pub fn new<T: Into<String>, U: Into<String>>(
+        property_id: u64,
+        renter: T,
+        rentee: U,
+        event: LeaseEvent,
+    ) -> Self {
+        LeaseChangedHookMsg {
+            property_id,
+            renter: renter.into(),
+            rentee: rentee.into(),
+            event,
+        }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = LeaseChangedExecuteMsg::LeaseChangedHook(self);
+        to_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+enum LeaseChangedExecuteMsg {
+    LeaseChangedHook(LeaseChangedHookMsg),
+}
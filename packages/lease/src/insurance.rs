@@ -0,0 +1,53 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Binary, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+
+/// InsurancePremiumMsg should be de/serialized under `InsurancePremium()` variant in a ExecuteMsg.
+/// It comes with the premium the rentee paid on top of the rent of the given property.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InsurancePremiumMsg {
+    pub property_id: u64,
+    pub rentee: String,
+    /// acudos rent of one period the premium was paid on
+    pub rent: Uint128,
+}
+
+impl InsurancePremiumMsg {
+    pub fn new<T: Into<String>>(property_id: u64, rentee: T, rent: Uint128) -> Self {
+        InsurancePremiumMsg {
+            property_id,
+            rentee: rentee.into(),
+            rent,
+        }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = InsurancePremiumExecuteMsg::InsurancePremium(self);
+        to_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct with the premium to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(
+        self,
+        contract_addr: T,
+        premium: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: premium,
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+enum InsurancePremiumExecuteMsg {
+    InsurancePremium(InsurancePremiumMsg),
+}
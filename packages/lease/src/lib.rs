@@ -0,0 +1,25 @@
+mod helpers;
+mod hook;
+mod insurance;
+mod msg;
+mod query;
+mod types;
+
+pub use crate::helpers::LeaseContract;
+pub use crate::hook::{LeaseChangedHookMsg, LeaseEvent};
+pub use crate::insurance::InsurancePremiumMsg;
+pub use crate::msg::{
+    ExecuteMsg, InstantiateMsg, InsuranceInfo, NewProperty, RentOffer, RentUpdate, SudoMsg,
+};
+pub use crate::query::{
+    ApplicantOffer, Application, AvailabilityResponse, AvailablePropertiesResponse,
+    BalanceResponse, BookingsResponse, ClaimablePayoutResponse, ConfigResponse,
+    DepositStakeResponse, FreeWindow, LandlordOverviewResponse, LeaseStatusResponse, LeaseSummary,
+    ListedProperty, OffersResponse, PauseStatusResponse, PermissionsResponse,
+    PropertyAtHeightResponse, PropertyOffer, PropertyResponse, QueryMsg, RentQuoteResponse,
+    ReputationResponse, SearchPropertiesResponse, TenantOverviewResponse, TotalPropertiesResponse,
+};
+pub use crate::types::{
+    Booking, Config, DepositStaking, FlatInfo, Grant, Insurance, LeaseAction, LeaseDocument,
+    LeaseTerm, Offer, PauseState, Permission, RentSchedule, Reputation,
+};
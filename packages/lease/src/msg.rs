@@ -0,0 +1,227 @@
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw0::Expiration;
+use cw4::MemberChangedHookMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    DepositStaking, LeaseAction, LeaseDocument, LeaseTerm, Permission, RentSchedule,
+};
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct InstantiateMsg {
+    /// cw4 group of verified landlords, anyone can list properties if not set
+    pub landlord_group: Option<String>,
+}
+/// Messages are snake_case like in the cw* packages. The PascalCase names used up to
+/// 0.1 are still accepted as aliases and will be dropped in 0.3.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// it is function to register property
+    #[serde(alias = "AddProperty")]
+    AddProperty {
+        rent: Uint128,
+        /// Applicants below this reputation score cannot request a lease
+        min_reputation: Option<i64>,
+        /// Reference currency the rent is given in, eg. "usd". The rent is converted
+        /// to acudos through the oracle when it is paid.
+        currency: Option<String>,
+    },
+    /// Lists several properties at once, they get consecutive ids
+    AddProperties { properties: Vec<NewProperty> },
+    /// Changes the rent of several listings of the sender that are not requested or rented
+    BulkUpdateRent { updates: Vec<RentUpdate> },
+    /// Accepts the pending rentees of several properties of the sender, without rent
+    /// schedules and documents
+    BulkAccept { property_ids: Vec<u64> },
+    /// Accepts the pending rentee, optionally agreeing on scheduled rent increases
+    #[serde(alias = "AcceptLease")]
+    AcceptLease {
+        property_id: u64,
+        rent_schedule: Option<RentSchedule>,
+        /// Rental agreement the renter signed, its hash must match the one of the request
+        document: Option<LeaseDocument>,
+    },
+    /// Requests a lease, optionally for a fixed term
+    #[serde(alias = "RequestForLease")]
+    RequestForLease {
+        property_id: u64,
+        term: Option<LeaseTerm>,
+        /// Highest oracle rate the rentee accepts for a rent in a reference currency
+        max_rate: Option<Decimal>,
+        /// Rental agreement the rentee signed
+        document: Option<LeaseDocument>,
+        /// Rent below the asking rent. Instead of requesting the property, twice the offered
+        /// rent is locked in an offer the renter can accept, reject or counter.
+        offer: Option<RentOffer>,
+    },
+    #[serde(alias = "TerminateLease")]
+    TerminateLease { property_id: u64 },
+    /// Pays the rent of the current period. For a rent in a reference currency
+    /// `max_rate` protects the rentee from the oracle rate moving against them.
+    #[serde(alias = "PayRent")]
+    PayRent {
+        property_id: u64,
+        max_rate: Option<Decimal>,
+    },
+    #[serde(alias = "RejectLease")]
+    RejectLease { property_id: u64 },
+    /// Extends a fixed term lease. The renter offers the term, the rentee accepts it
+    /// by sending the same term.
+    RenewLease { property_id: u64, term: LeaseTerm },
+    /// Closes a fixed term lease after its end and returns the deposit to the rentee
+    ClaimDeposit { property_id: u64 },
+    /// Change the admin managing the hooks
+    UpdateAdmin { admin: Option<String> },
+    /// Add a new hook to be informed of all lease lifecycle transitions. Only the admin can do this
+    AddHook { addr: String },
+    /// Remove a hook. Only the admin can do this
+    RemoveHook { addr: String },
+    /// Records a dispute about the lease on the property as lost by `loser`,
+    /// who must be its renter or rentee. Only the admin can do this
+    RecordDisputeLost { property_id: u64, loser: String },
+    /// Changes the platform parameters. Only the admin can do this
+    UpdateConfig {
+        lease_period: Option<u64>,
+        #[serde(default)]
+        lease_period_seconds: Option<u64>,
+        max_batch_size: Option<u32>,
+        pull_rent: Option<bool>,
+    },
+    /// Sets the cw4 group whose members may list properties, or lets anyone list
+    /// with None. Only the admin can do this
+    UpdateLandlordGroup { landlord_group: Option<String> },
+    /// Sent by the landlord group when members change. Listings of landlords whose
+    /// weight drops to zero are suspended, and reinstated once they are members again.
+    /// Pending requests on suspended listings can be withdrawn by their rentee with RejectLease.
+    MemberChangedHook(MemberChangedHookMsg),
+    /// Sets the insurance pool the premiums are forwarded to, or stops collecting
+    /// premiums with None. Only the admin can do this
+    UpdateInsurance { insurance: Option<InsuranceInfo> },
+    /// Sets the price oracle converting rents given in a reference currency.
+    /// Only the admin can do this
+    UpdateOracle { oracle: Option<String> },
+    /// Delegates the deposits of leases accepted from now on to a validator, or keeps them
+    /// in the contract with None. Only the admin can do this
+    UpdateStaking { staking: Option<DepositStaking> },
+    /// Lets a manager act for the sender on one property, or on all its properties
+    /// with None, until `expires`. Replaces an earlier grant with the same scope.
+    GrantManager {
+        manager: String,
+        property_id: Option<u64>,
+        permissions: Vec<Permission>,
+        expires: Option<Expiration>,
+    },
+    /// Removes the grant of the sender to the manager with the given scope
+    RevokeManager {
+        manager: String,
+        property_id: Option<u64>,
+    },
+    /// Stops the given actions, or all of them with None. Only the admin can do this
+    Pause { actions: Option<Vec<LeaseAction>> },
+    /// Resumes the given actions, or lifts the whole pause with None. Only the admin can do this
+    Unpause { actions: Option<Vec<LeaseAction>> },
+    /// Sends the sender the acudos of its payouts that failed
+    ClaimPayout {},
+    /// Sends the sender its deposits whose unbonding finished
+    ClaimUnbonded {},
+    /// Sends the sender the given coins of its accrued rent, or all of it with None
+    Withdraw { amount: Option<Vec<Coin>> },
+    /// Starts the lease at the offered rent, which becomes the rent of the property
+    AcceptOffer {
+        property_id: u64,
+        applicant: String,
+        /// Rental agreement the renter signed, its hash must match the one of the offer
+        document: Option<LeaseDocument>,
+    },
+    /// Refunds the offer to the applicant
+    RejectOffer { property_id: u64, applicant: String },
+    /// Proposes a higher rent to the applicant, replacing an earlier counter offer
+    CounterOffer {
+        property_id: u64,
+        applicant: String,
+        rent: Uint128,
+        /// Rental agreement the renter signed, its hash must match the one of the offer
+        document: Option<LeaseDocument>,
+    },
+    /// Starts the lease at the rent the renter countered with. The sender tops up the
+    /// escrow to twice the countered rent.
+    AcceptCounter {
+        property_id: u64,
+        /// Highest oracle rate the applicant accepts for a rent in a reference currency
+        max_rate: Option<Decimal>,
+    },
+    /// Withdraws the offer of the sender and refunds it, also once it has expired
+    WithdrawOffer { property_id: u64 },
+    /// Sets the acudos charged per started day of a booked stay, or stops taking bookings
+    /// with None. Bookings already made keep their price
+    SetDailyRate {
+        property_id: u64,
+        daily_rate: Option<Uint128>,
+    },
+    /// Books the property from `start` until `end`, both given as time, on a property without
+    /// a lease. The stay must not overlap other bookings. The sender locks the daily rate
+    /// for every started day of the stay, the excess is refunded.
+    BookProperty {
+        property_id: u64,
+        start: Expiration,
+        end: Expiration,
+    },
+    /// Cancels the booking starting at `start` before the stay begins and refunds the guest.
+    /// Sent by the guest, or by the renter and its managers
+    CancelBooking { property_id: u64, start: Expiration },
+    /// Releases the escrow of the booking starting at `start` to the renter once the stay
+    /// is over and frees its interval. Anyone can send it
+    CloseBooking { property_id: u64, start: Expiration },
+    /// Refunds all escrowed funds to the rentees, pro rata if the contract holds less than
+    /// it owes. Only the admin can do this, while the whole contract is paused. Up to `limit`
+    /// escrows (at most the batch limit) are handled per call, the next call resumes until
+    /// all are refunded
+    EmergencyWithdraw {
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+}
+
+/// See RequestForLease
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct RentOffer {
+    /// Rent per period, in acudos or in units of the currency of the property
+    pub rent: Uint128,
+    /// One lease period from now if not set
+    pub expires: Option<Expiration>,
+}
+
+/// A property listed with AddProperties, see AddProperty
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct NewProperty {
+    pub rent: Uint128,
+    pub min_reputation: Option<i64>,
+    pub currency: Option<String>,
+}
+
+/// New rent of a listing, in the currency it was listed in
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct RentUpdate {
+    pub property_id: u64,
+    pub rent: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct InsuranceInfo {
+    /// Contract receiving the premiums as InsurancePremiumMsg
+    pub pool: String,
+    /// Share of the rent paid on top of every rent payment, between 0 and 1
+    pub premium: Decimal,
+}
+
+/// Messages only the chain can send, eg. from a begin blocker
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Looks at up to `limit` properties, capped by the batch limit, and closes the leases
+    /// among them that defaulted or reached the end of their term. Each sweep continues
+    /// where the previous one stopped and starts over after the last property.
+    ProcessExpired { limit: u32 },
+}
@@ -0,0 +1,328 @@
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw0::Expiration;
+use cw4::Cw4Contract;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    Booking, DepositStaking, FlatInfo, Grant, Insurance, LeaseAction, LeaseDocument, LeaseTerm,
+    Offer, RentSchedule, Reputation,
+};
+
+/// snake_case, the legacy PascalCase names are accepted as aliases until 0.3
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns PropertyResponse
+    #[serde(alias = "PropertyDetail")]
+    PropertyDetail(u64),
+    /// Returns PropertyAtHeightResponse with the property as it was at the beginning of
+    /// the given block height
+    PropertyAtHeight { property_id: u64, height: u64 },
+    /// Returns LeaseStatusResponse with the rent due for the current period
+    LeaseStatus { property_id: u64 },
+    /// Returns AvailablePropertiesResponse
+    #[serde(alias = "ShowAllAvailableProperties")]
+    ShowAllAvailableProperties,
+    /// Returns TotalPropertiesResponse
+    #[serde(alias = "GetTotalProperties")]
+    GetTotalProperties,
+    /// Return AdminResponse
+    Admin {},
+    /// Shows all registered hooks. Returns HooksResponse.
+    Hooks {},
+    /// Returns ReputationResponse with the stats and score of the address
+    Reputation { address: String },
+    /// Returns ConfigResponse with the current platform parameters
+    Config {},
+    /// Returns RentQuoteResponse with the rent due now, converted to acudos
+    RentQuote { property_id: u64 },
+    /// Returns PermissionsResponse with the unexpired grants of the renter to the manager
+    Permissions { renter: String, manager: String },
+    /// Returns PauseStatusResponse with the actions paused by the admin
+    PauseStatus {},
+    /// Returns ClaimablePayoutResponse with the acudos of failed payouts to the address
+    ClaimablePayout { address: String },
+    /// Returns BalanceResponse with the rent accrued for the address
+    Balance { address: String },
+    /// Returns TenantOverviewResponse with the leases, requests and offers of the address
+    /// as rentee, ordered by property id
+    TenantOverview {
+        address: String,
+        /// Id of the last property of the previous page
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns LandlordOverviewResponse with the leases and requests on the properties
+    /// listed by the address, ordered by property id
+    LandlordOverview {
+        address: String,
+        /// Id of the last property of the previous page
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns SearchPropertiesResponse with the properties open for requests within the
+    /// rent range, ordered by rent. Rents are compared in `denom`, acudos if not set,
+    /// which also matches rents in a reference currency of that name. With a renter only
+    /// its listings are searched and they are ordered by id.
+    SearchProperties {
+        min_rent: Option<Uint128>,
+        max_rent: Option<Uint128>,
+        denom: Option<String>,
+        renter: Option<String>,
+        /// Id of the last property of the previous page
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns cw_controllers ClaimsResponse with the unbonding deposits of the address
+    Claims { address: String },
+    /// Returns DepositStakeResponse with the delegation of the deposit held for the property
+    DepositStake { property_id: u64 },
+    /// Returns BookingsResponse with the bookings on the calendar of the property,
+    /// ordered by start
+    Bookings {
+        property_id: u64,
+        start_after: Option<Expiration>,
+        limit: Option<u32>,
+    },
+    /// Returns AvailabilityResponse with the free windows of the property between `from`,
+    /// now if not set, and `until`
+    Availability {
+        property_id: u64,
+        from: Option<Expiration>,
+        until: Expiration,
+        limit: Option<u32>,
+    },
+    /// Returns OffersResponse with the open offers on the property, expired ones included
+    Offers {
+        property_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct PropertyResponse {
+    pub property: FlatInfo,
+    /// Set while the renter is not a member of the landlord group, no new lease can be requested
+    #[serde(default)]
+    pub suspended: bool,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct PropertyAtHeightResponse {
+    /// None if the property was not listed yet at that height
+    pub property: Option<FlatInfo>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct AvailablePropertiesResponse {
+    pub properties: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct TotalPropertiesResponse {
+    /// Number of properties ever listed
+    pub total: u64,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct LeaseStatusResponse {
+    pub rentee: Option<String>,
+    pub expires: Option<Expiration>,
+    /// Amount due for the current period, None if the lease is not accepted yet
+    pub rent_due: Option<Uint128>,
+    pub rent_schedule: Option<RentSchedule>,
+    /// Rent of the expired period is accepted as late until then, afterwards the rentee
+    /// is in default
+    pub grace_ends: Option<Expiration>,
+    /// End of a fixed term lease
+    pub ends: Option<Expiration>,
+    /// True once the fixed term is over and the deposit can be claimed
+    pub ended: bool,
+    /// Renewal offered by the renter, waiting for the rentee
+    pub renewal: Option<LeaseTerm>,
+    /// Rental agreement the lease is bound to
+    pub document: Option<LeaseDocument>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct RentQuoteResponse {
+    /// Rent due for the current period in units of `currency`, or in acudos
+    pub rent: Uint128,
+    pub currency: Option<String>,
+    /// Oracle rate used for the conversion
+    pub rate: Option<Decimal>,
+    /// acudos to pay for the current period
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct PermissionsResponse {
+    /// Grant covering every property of the renter
+    pub all_properties: Option<Grant>,
+    /// Grants for single properties, by property id
+    pub properties: Vec<(u64, Grant)>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ConfigResponse {
+    /// Number of blocks covered by one rent payment
+    pub lease_period: u64,
+    /// Number of seconds covered by one rent payment of a lease whose term ends at a time
+    pub lease_period_seconds: u64,
+    /// Most units handled by a single AddProperties, BulkUpdateRent or BulkAccept
+    pub max_batch_size: u32,
+    /// Rent accrues in a balance of the renter, who withdraws it, instead of being sent
+    /// with every payment
+    pub pull_rent: bool,
+    /// cw4 group of verified landlords. If set, only its members can list properties.
+    pub landlord_group: Option<Cw4Contract>,
+    /// Rent insurance every rentee pays a premium into
+    pub insurance: Option<Insurance>,
+    /// Price oracle converting rents given in a reference currency to acudos
+    pub oracle: Option<Addr>,
+    /// Validator the deposits of accepted leases are delegated to while they are held
+    pub staking: Option<DepositStaking>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct PauseStatusResponse {
+    /// Every action is stopped
+    pub all: bool,
+    /// Actions stopped on their own
+    pub actions: Vec<LeaseAction>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ClaimablePayoutResponse {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct BalanceResponse {
+    pub balance: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct DepositStakeResponse {
+    /// None while the deposit is held by the contract
+    pub validator: Option<String>,
+    pub amount: Uint128,
+    /// Rewards earned by the deposit so far, split between rentee and renter when the
+    /// lease is closed
+    pub rewards: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct BookingsResponse {
+    pub bookings: Vec<Booking>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct AvailabilityResponse {
+    pub windows: Vec<FreeWindow>,
+}
+
+/// Interval without bookings, a stay from `start` until `end` can be booked
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct FreeWindow {
+    pub start: Expiration,
+    pub end: Expiration,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct OffersResponse {
+    pub offers: Vec<ApplicantOffer>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ApplicantOffer {
+    pub applicant: String,
+    pub offer: Offer,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct SearchPropertiesResponse {
+    pub properties: Vec<ListedProperty>,
+}
+
+/// Property open for requests, see SearchProperties
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ListedProperty {
+    pub property_id: u64,
+    pub renter: String,
+    pub rent: Uint128,
+    pub currency: Option<String>,
+    pub min_reputation: Option<i64>,
+}
+
+/// Accepted lease, see TenantOverview and LandlordOverview
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct LeaseSummary {
+    pub property_id: u64,
+    pub renter: String,
+    pub rentee: String,
+    /// Rent due for the current period, in acudos or in units of `currency`
+    pub rent_due: Uint128,
+    pub currency: Option<String>,
+    /// End of the paid periods, the next rent is due by then
+    pub expires: Expiration,
+    /// End of a fixed term lease
+    pub ends: Option<Expiration>,
+    /// Security deposit held by the contract
+    pub escrow: Uint128,
+}
+
+/// Request for lease waiting for the renter
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Application {
+    pub property_id: u64,
+    pub renter: String,
+    pub rentee: String,
+    pub term: Option<LeaseTerm>,
+    /// First rent and deposit locked by the rentee
+    pub escrow: Uint128,
+}
+
+/// Offer of a rentee waiting for the renter, see TenantOverview
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct PropertyOffer {
+    pub property_id: u64,
+    pub offer: Offer,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct TenantOverviewResponse {
+    pub leases: Vec<LeaseSummary>,
+    pub applications: Vec<Application>,
+    pub offers: Vec<PropertyOffer>,
+    /// acudos the address has locked in the contract for the leases, requests and offers
+    /// of the page
+    pub escrow: Uint128,
+    /// Earliest date a rent of the page is due
+    pub next_due: Option<Expiration>,
+    /// acudos of rent paid over all leases
+    pub paid: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct LandlordOverviewResponse {
+    /// Number of properties listed by the address
+    pub properties: u64,
+    pub leases: Vec<LeaseSummary>,
+    pub applications: Vec<Application>,
+    /// acudos rentees have locked for the properties of the page
+    pub escrow: Uint128,
+    /// Earliest date a rent of the page is due
+    pub next_due: Option<Expiration>,
+    /// acudos of rent received over all leases
+    pub received: Uint128,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct ReputationResponse {
+    pub address: String,
+    pub stats: Reputation,
+    pub score: i64,
+}
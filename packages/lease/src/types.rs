@@ -0,0 +1,276 @@
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Timestamp, Uint128};
+use cw0::{Duration, Expiration};
+use cw4::Cw4Contract;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct FlatInfo {
+    pub renter: String,
+    pub rentee: Option<String>,
+    /// Rent per period, in acudos or in units of `currency`
+    pub rent: Uint128,
+    /// Reference currency of the rent (and of the rent schedule), converted to acudos
+    /// through the oracle whenever rent is paid
+    pub currency: Option<String>,
+    pub expires: Option<Expiration>,
+    /// Rent increases agreed on when the lease was accepted
+    pub rent_schedule: Option<RentSchedule>,
+    /// Block height at which the current lease was accepted
+    pub accepted_at: Option<u64>,
+    /// Block time at which the current lease was accepted
+    #[serde(default)]
+    pub accepted_time: Option<Timestamp>,
+    /// Length of a rent period of the current lease, fixed at acceptance. Changing the
+    /// configured lease period does not touch running leases. It is given in time for a
+    /// term ending at a time, so the paid period compares to the end, in blocks otherwise.
+    #[serde(default)]
+    pub lease_period: Option<Duration>,
+    /// Rent per period agreed on in an accepted offer, due for the current lease instead of
+    /// the listed rent
+    #[serde(default)]
+    pub agreed_rent: Option<Uint128>,
+    /// Term requested by the rentee, None for a lease running until it is terminated
+    pub term: Option<LeaseTerm>,
+    /// End of the accepted lease term, the deposit becomes claimable afterwards. The lease
+    /// counts as completed if `expires` reached it.
+    pub ends: Option<Expiration>,
+    /// Renewal offered by the renter and waiting for the rentee to accept
+    pub renewal: Option<LeaseTerm>,
+    /// Funds of the rentee held by the contract (first rent and deposit, or just the deposit)
+    pub escrow: Uint128,
+    /// Reputation score an applicant needs to request a lease
+    pub min_reputation: Option<i64>,
+    /// Rental agreement both parties signed off on, kept until the lease is closed
+    pub document: Option<LeaseDocument>,
+    /// acudos charged per started day of a stay booked on the calendar, no bookings are
+    /// taken while it is not set
+    #[serde(default)]
+    pub daily_rate: Option<Uint128>,
+}
+
+impl FlatInfo {
+    /// Removes the rentee and all data of its lease, the property is available again
+    pub fn clear_lease(&mut self) {
+        self.rentee = None;
+        self.expires = None;
+        self.rent_schedule = None;
+        self.accepted_at = None;
+        self.accepted_time = None;
+        self.lease_period = None;
+        self.agreed_rent = None;
+        self.term = None;
+        self.ends = None;
+        self.renewal = None;
+        self.escrow = Uint128::zero();
+        self.document = None;
+    }
+}
+
+/// Off-chain rental agreement a lease is bound to
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct LeaseDocument {
+    /// Hex encoded sha256 hash of the signed document, eg. the PDF of the rental contract
+    pub hash: String,
+    /// Where the document can be retrieved
+    pub uri: Option<String>,
+}
+
+/// Fixed term of a lease, counted from acceptance (or from the current end on renewal).
+/// A renewal has to end in the unit of the current term.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseTerm {
+    /// The lease runs for the given number of lease periods, at most 1200
+    Periods(u64),
+    /// The lease runs until the given expiration. For an end in time, rent periods are
+    /// counted in time as well.
+    Until(Expiration),
+}
+
+/// Describes how the rent of an accepted lease changes over its lifetime.
+/// Until the first change kicks in, the listed rent is due.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RentSchedule {
+    /// Once an expiration is reached its rent is due, until the next one is reached.
+    /// Steps must be given in ascending order.
+    Steps(Vec<(Expiration, Uint128)>),
+    /// Rent grows by `increase` (eg. 0.03 for +3%, at most 1) every `every_periods` lease
+    /// periods, counted from the acceptance of the lease.
+    Percentage {
+        increase: Decimal,
+        every_periods: u64,
+    },
+}
+
+/// Actions a renter can delegate to a property manager
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// AcceptLease, RejectLease, BulkAccept, AcceptOffer, RejectOffer, CounterOffer and
+    /// CancelBooking
+    AcceptReject,
+    /// TerminateLease
+    Terminate,
+    /// BulkUpdateRent and SetDailyRate
+    Reprice,
+}
+
+/// Permissions a renter granted to a manager, for one or for all of its properties
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Grant {
+    pub permissions: Vec<Permission>,
+    pub expires: Expiration,
+}
+
+impl Grant {
+    /// True if the grant includes the permission and has not expired yet
+    pub fn allows(&self, permission: Permission, block: &BlockInfo) -> bool {
+        !self.expires.is_expired(block) && self.permissions.contains(&permission)
+    }
+}
+
+/// Groups of messages the admin can pause
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseAction {
+    /// AddProperty and AddProperties
+    AddProperty,
+    /// BulkUpdateRent and SetDailyRate
+    UpdateRent,
+    RequestForLease,
+    /// AcceptLease and BulkAccept
+    AcceptLease,
+    RejectLease,
+    TerminateLease,
+    PayRent,
+    RenewLease,
+    ClaimDeposit,
+    GrantManager,
+    ClaimPayout,
+    Withdraw,
+    /// CounterOffer and WithdrawOffer. Offers are made with RequestForLease, accepted with
+    /// AcceptOffer and AcceptCounter like AcceptLease and rejected like RejectLease.
+    Offer,
+    ClaimUnbonded,
+    /// BookProperty, CancelBooking and CloseBooking
+    Booking,
+}
+
+/// Circuit breaker of the admin, stopping all or some actions
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Default)]
+pub struct PauseState {
+    /// Every action is stopped
+    pub all: bool,
+    /// Actions stopped on their own
+    pub actions: Vec<LeaseAction>,
+}
+
+impl PauseState {
+    pub fn is_paused(&self, action: LeaseAction) -> bool {
+        self.all || self.actions.contains(&action)
+    }
+}
+
+/// Rent offered below the asking rent by an applicant, see RequestForLease
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Offer {
+    /// Offered rent per period, in acudos or in units of the currency of the property
+    pub rent: Uint128,
+    /// Rent per period the renter countered with
+    pub counter: Option<Uint128>,
+    /// Rental agreement the renter signed with the counter offer
+    #[serde(default)]
+    pub counter_document: Option<LeaseDocument>,
+    /// acudos locked by the applicant, the first rent and deposit at the offered rent
+    pub escrow: Uint128,
+    pub expires: Expiration,
+    pub term: Option<LeaseTerm>,
+    pub document: Option<LeaseDocument>,
+}
+
+/// Stay booked in advance on the calendar of a property, see BookProperty
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Booking {
+    pub guest: Addr,
+    pub start: Expiration,
+    /// End of the stay, the next booking may start at it
+    pub end: Expiration,
+    /// acudos locked by the guest, released to the renter once the stay is over
+    pub escrow: Uint128,
+}
+
+/// Platform parameters, changed by the admin (eg. the landlord association multisig)
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Number of blocks covered by one rent payment
+    pub lease_period: u64,
+    /// Number of seconds covered by one rent payment of a lease whose term ends at a time
+    #[serde(default = "default_lease_period_seconds")]
+    pub lease_period_seconds: u64,
+    /// Most units handled by a single AddProperties, BulkUpdateRent or BulkAccept
+    pub max_batch_size: u32,
+    /// Rent accrues in a balance of the renter, who withdraws it, instead of being sent
+    /// with every payment
+    #[serde(default)]
+    pub pull_rent: bool,
+    /// cw4 group of verified landlords. If set, only its members can list properties.
+    pub landlord_group: Option<Cw4Contract>,
+    /// Rent insurance every rentee pays a premium into
+    pub insurance: Option<Insurance>,
+    /// Price oracle converting rents given in a reference currency to acudos
+    pub oracle: Option<Addr>,
+    /// Validator the deposits of accepted leases are delegated to while they are held
+    #[serde(default)]
+    pub staking: Option<DepositStaking>,
+}
+
+// lease period in time of configs stored before it was added, thirty days
+fn default_lease_period_seconds() -> u64 {
+    30 * 24 * 60 * 60
+}
+
+/// Delegation of escrowed deposits, see UpdateStaking
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct DepositStaking {
+    pub validator: String,
+    /// Unbonding period of the chain, deposits are claimable with ClaimUnbonded after it
+    pub unbonding_period: Duration,
+    /// Share of the staking rewards going to the rentee, the rest goes to the renter
+    pub rentee_share: Decimal,
+}
+
+/// Insurance pool covering renters against rentees defaulting on rent
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Insurance {
+    pub pool: Addr,
+    /// Share of the rent paid on top of every rent payment, eg. 0.02 for 2%
+    pub premium: Decimal,
+}
+
+/// Track record of an address, collected over all its leases as renter or rentee
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Default)]
+pub struct Reputation {
+    /// Fixed term leases that ran until their end
+    pub leases_completed: u64,
+    /// Rent paid before the lease period expired
+    pub on_time_payments: u64,
+    /// Rent paid after the lease period expired
+    pub late_payments: u64,
+    /// Leases terminated by the renter because the rentee defaulted on rent
+    pub terminations_for_default: u64,
+    /// Disputes the admin decided against this address
+    pub disputes_lost: u64,
+}
+
+impl Reputation {
+    /// Single score used for `min_reputation`: completed leases count twice, every
+    /// on time payment once, late payments cost two, disputes three and defaults five.
+    pub fn score(&self) -> i64 {
+        2 * self.leases_completed as i64 + self.on_time_payments as i64
+            - 2 * self.late_payments as i64
+            - 3 * self.disputes_lost as i64
+            - 5 * self.terminations_for_default as i64
+    }
+}
@@ -4,7 +4,7 @@ use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{
     from_slice, to_vec, Addr, Api, Binary, BlockInfo, Coin, ContractResult, CosmosMsg, CustomQuery,
     Empty, Querier, QuerierResult, QuerierWrapper, QueryRequest, Storage, SystemError,
-    SystemResult,
+    SystemResult, Uint128, Validator,
 };
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
@@ -14,6 +14,7 @@ use crate::bank::Bank;
 use crate::contracts::Contract;
 use crate::custom_handler::{CustomHandler, PanickingCustomHandler};
 use crate::executor::{AppResponse, Executor};
+use crate::staking::{StakeKeeper, Staking};
 use crate::transactions::transactional;
 use crate::wasm::{ContractData, Wasm, WasmKeeper};
 use crate::BankKeeper;
@@ -76,6 +77,7 @@ where
 pub struct AppBuilder<ExecC, QueryC> {
     wasm: Option<Box<dyn Wasm<ExecC, QueryC>>>,
     bank: Option<Box<dyn Bank>>,
+    staking: Option<Box<dyn Staking>>,
     api: Option<Box<dyn Api>>,
     storage: Option<Box<dyn Storage>>,
     custom: Option<Box<dyn CustomHandler<ExecC, QueryC>>>,
@@ -103,6 +105,17 @@ where
         self
     }
 
+    /// Overwrites default staking interface
+    #[track_caller]
+    pub fn with_staking(mut self, staking: impl Staking + 'static) -> Self {
+        assert!(
+            self.staking.is_none(),
+            "Staking interface already overwritten"
+        );
+        self.staking = Some(Box::new(staking));
+        self
+    }
+
     /// Overwrites default api interface
     #[track_caller]
     pub fn with_api(mut self, api: impl Api + 'static) -> Self {
@@ -144,6 +157,9 @@ where
     pub fn build(self) -> App<ExecC, QueryC> {
         let wasm = self.wasm.unwrap_or_else(|| Box::new(WasmKeeper::new()));
         let bank = self.bank.unwrap_or_else(|| Box::new(BankKeeper::new()));
+        let staking = self
+            .staking
+            .unwrap_or_else(|| Box::new(StakeKeeper::default()));
         let api = self.api.unwrap_or_else(|| Box::new(MockApi::default()));
         let storage = self.storage.unwrap_or_else(|| Box::new(MockStorage::new()));
         let block = self.block.unwrap_or_else(|| mock_env().block);
@@ -151,7 +167,12 @@ where
             .custom
             .unwrap_or_else(|| Box::new(PanickingCustomHandler));
 
-        let router = Router { wasm, bank, custom };
+        let router = Router {
+            wasm,
+            bank,
+            staking,
+            custom,
+        };
 
         App {
             router,
@@ -183,7 +204,7 @@ where
 
     /// Simple helper so we get access to all the QuerierWrapper helpers,
     /// eg. wrap().query_wasm_smart, query_all_balances, ...
-    pub fn wrap(&self) -> QuerierWrapper {
+    pub fn wrap(&self) -> QuerierWrapper<'_> {
         QuerierWrapper::new(self)
     }
 
@@ -220,6 +241,25 @@ where
             .init_balance(&mut *self.storage, account, amount)
     }
 
+    /// This is an "admin" function to add a validator delegations can go to
+    pub fn add_validator(&mut self, validator: Validator) -> AnyResult<()> {
+        self.router
+            .staking
+            .add_validator(&mut *self.storage, validator)
+    }
+
+    /// This is an "admin" function to let staking rewards accrue to a delegation
+    pub fn add_staking_rewards(
+        &mut self,
+        delegator: &Addr,
+        validator: &str,
+        amount: Uint128,
+    ) -> AnyResult<()> {
+        self.router
+            .staking
+            .add_rewards(&mut *self.storage, delegator, validator, amount)
+    }
+
     /// This registers contract code (like uploading wasm bytecode on a chain),
     /// so it can later be used to instantiate a contract.
     pub fn store_code(&mut self, code: Box<dyn Contract<ExecC>>) -> u64 {
@@ -254,6 +294,7 @@ where
 pub struct Router<ExecC, QueryC> {
     pub(crate) wasm: Box<dyn Wasm<ExecC, QueryC>>,
     pub(crate) bank: Box<dyn Bank>,
+    pub(crate) staking: Box<dyn Staking>,
     pub(crate) custom: Box<dyn CustomHandler<ExecC, QueryC>>,
 }
 
@@ -292,6 +333,7 @@ where
                     .query(api, storage, &self.querier(api, storage, block), block, req)
             }
             QueryRequest::Bank(req) => self.bank.query(api, storage, req),
+            QueryRequest::Staking(req) => self.staking.query(api, storage, req),
             QueryRequest::Custom(req) => self.custom.query(api, storage, block, req),
             _ => unimplemented!(),
         }
@@ -306,8 +348,13 @@ where
         msg: CosmosMsg<ExecC>,
     ) -> AnyResult<AppResponse> {
         match msg {
-            CosmosMsg::Wasm(msg) => self.wasm.execute(api, storage, &self, block, sender, msg),
+            CosmosMsg::Wasm(msg) => self.wasm.execute(api, storage, self, block, sender, msg),
             CosmosMsg::Bank(msg) => self.bank.execute(storage, sender, msg),
+            CosmosMsg::Staking(msg) => self.staking.execute(api, storage, &*self.bank, sender, msg),
+            CosmosMsg::Distribution(msg) => {
+                self.staking
+                    .execute_distribution(api, storage, &*self.bank, sender, msg)
+            }
             CosmosMsg::Custom(msg) => self.custom.execute(api, storage, block, sender, msg),
             _ => unimplemented!(),
         }
@@ -353,7 +400,7 @@ where
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
-                    error: format!("Parsing query request: {}", e.to_string()),
+                    error: format!("Parsing query request: {}", e),
                     request: bin_request.into(),
                 })
             }
@@ -369,12 +416,12 @@ where
 #[cfg(test)]
 mod test {
     use cosmwasm_std::{
-        coin, coins, to_binary, AllBalanceResponse, Attribute, BankMsg, BankQuery, Event, Reply,
-        StdResult, SubMsg, WasmMsg,
+        coin, coins, to_binary, AllBalanceResponse, Attribute, BankMsg, BankQuery, Decimal, Event,
+        Reply, StdResult, SubMsg, WasmMsg, WasmQuery,
     };
 
     use crate::error::Error;
-    use crate::test_helpers::contracts::{echo, hackatom, payout, reflect};
+    use crate::test_helpers::contracts::{echo, hackatom, oracle, payout, reflect};
     use crate::test_helpers::{CustomMsg, EmptyMsg};
     use crate::transactions::StorageTransaction;
     use cosmwasm_std::{OverflowError, OverflowOperation, StdError};
@@ -448,6 +495,42 @@ mod test {
         assert_eq!(vec![coin(15, "btc"), coin(70, "eth")], rich);
     }
 
+    #[test]
+    fn smart_query_oracle() {
+        let mut app = mock_app();
+        let owner = Addr::unchecked("owner");
+
+        let code_id = app.store_code(oracle::contract());
+        let oracle_addr = app
+            .instantiate_contract(code_id, owner.clone(), &EmptyMsg {}, &[], "Oracle", None)
+            .unwrap();
+
+        // unknown currencies have no price
+        let price = oracle::QueryMsg::Price {
+            currency: "usd".to_owned(),
+        };
+        let err = app
+            .wrap()
+            .query_wasm_smart::<oracle::PriceResponse>(&oracle_addr, &price)
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{}", err);
+
+        let msg = oracle::ExecuteMsg::SetPrice {
+            currency: "usd".to_owned(),
+            rate: Decimal::percent(250),
+        };
+        app.execute_contract(owner, oracle_addr.clone(), &msg, &[])
+            .unwrap();
+
+        // read back through a raw WasmQuery::Smart request
+        let request = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: oracle_addr.to_string(),
+            msg: to_binary(&price).unwrap(),
+        });
+        let res: oracle::PriceResponse = app.wrap().query(&request).unwrap();
+        assert_eq!(res.rate, Decimal::percent(250));
+    }
+
     #[test]
     fn simple_contract() {
         let mut app = mock_app();
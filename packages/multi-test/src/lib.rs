@@ -12,7 +12,10 @@ mod contracts;
 pub mod custom_handler;
 pub mod error;
 mod executor;
-mod test_helpers;
+mod staking;
+/// Simple contracts for tests, also available to other crates with the `test-helpers` feature
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_helpers;
 mod transactions;
 mod wasm;
 
@@ -21,4 +24,5 @@ pub use crate::bank::{Bank, BankKeeper};
 pub use crate::contracts::{Contract, ContractWrapper};
 pub use crate::custom_handler::CustomHandler;
 pub use crate::executor::{AppResponse, Executor};
+pub use crate::staking::{StakeKeeper, Staking, BONDED_POOL};
 pub use crate::wasm::{parse_contract_addr, Wasm, WasmKeeper};
@@ -0,0 +1,453 @@
+use cosmwasm_std::{
+    coin, from_slice, to_binary, Addr, AllBalanceResponse, AllDelegationsResponse,
+    AllValidatorsResponse, Api, BankMsg, BankQuery, Binary, BondedDenomResponse, Coin, Delegation,
+    DistributionMsg, Event, FullDelegation, Order, StakingMsg, StakingQuery, Storage, Uint128,
+    Validator, ValidatorResponse,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::bank::Bank;
+use crate::executor::AppResponse;
+use cosmwasm_storage::{prefixed, prefixed_read};
+use cw0::NativeBalance;
+use cw_storage_plus::Map;
+
+use anyhow::{bail, Result as AnyResult};
+
+const VALIDATORS: Map<&str, Validator> = Map::new("validators");
+/// Bonded amount keyed by (delegator, validator)
+const DELEGATIONS: Map<(&Addr, &str), Uint128> = Map::new("delegations");
+/// Rewards not withdrawn yet, keyed by (delegator, validator)
+const REWARDS: Map<(&Addr, &str), Uint128> = Map::new("rewards");
+
+/// Response to StakingQuery::Delegation, which cosmwasm-std does not export
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct DelegationResponse {
+    delegation: Option<FullDelegation>,
+}
+
+pub const NAMESPACE_STAKING: &[u8] = b"staking";
+
+/// Account holding the bonded tokens
+pub const BONDED_POOL: &str = "bonded_tokens_pool";
+
+/// Staking is a minimal interface of the staking and distribution modules.
+/// It is initialized outside of the trait
+pub trait Staking {
+    fn execute(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        bank: &dyn Bank,
+        sender: Addr,
+        msg: StakingMsg,
+    ) -> AnyResult<AppResponse>;
+
+    fn execute_distribution(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        bank: &dyn Bank,
+        sender: Addr,
+        msg: DistributionMsg,
+    ) -> AnyResult<AppResponse>;
+
+    fn query(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        request: StakingQuery,
+    ) -> AnyResult<Binary>;
+
+    // Admin interface
+    fn add_validator(&self, storage: &mut dyn Storage, validator: Validator) -> AnyResult<()>;
+
+    /// Lets rewards accrue to the delegation, they are minted when withdrawn
+    fn add_rewards(
+        &self,
+        storage: &mut dyn Storage,
+        delegator: &Addr,
+        validator: &str,
+        amount: Uint128,
+    ) -> AnyResult<()>;
+}
+
+/// Stand-in for the staking module. Rewards are added by hand with `App::add_staking_rewards`
+/// and are withdrawn automatically whenever the delegation changes, like in the Cosmos SDK.
+/// Unbonding completes immediately, so contracts have to enforce unbonding periods themselves.
+pub struct StakeKeeper {
+    bonded_denom: String,
+}
+
+impl Default for StakeKeeper {
+    fn default() -> Self {
+        StakeKeeper::new("stake")
+    }
+}
+
+impl StakeKeeper {
+    pub fn new<T: Into<String>>(bonded_denom: T) -> Self {
+        StakeKeeper {
+            bonded_denom: bonded_denom.into(),
+        }
+    }
+
+    fn delegation(
+        &self,
+        staking_storage: &dyn Storage,
+        delegator: &Addr,
+        validator: &str,
+    ) -> AnyResult<Option<FullDelegation>> {
+        let amount = DELEGATIONS.may_load(staking_storage, (delegator, validator))?;
+        let amount = match amount {
+            Some(amount) => coin(amount.u128(), &self.bonded_denom),
+            None => return Ok(None),
+        };
+        let rewards = REWARDS
+            .may_load(staking_storage, (delegator, validator))?
+            .unwrap_or_default();
+        let accumulated_rewards = match rewards.is_zero() {
+            true => vec![],
+            false => vec![coin(rewards.u128(), &self.bonded_denom)],
+        };
+        Ok(Some(FullDelegation {
+            delegator: delegator.clone(),
+            validator: validator.to_string(),
+            can_redelegate: amount.clone(),
+            amount,
+            accumulated_rewards,
+        }))
+    }
+
+    // mints the rewards of the delegation to the delegator
+    fn withdraw_rewards(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        bank: &dyn Bank,
+        delegator: &Addr,
+        validator: &str,
+    ) -> AnyResult<Vec<Event>> {
+        let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+        let rewards = REWARDS
+            .may_load(&staking_storage, (delegator, validator))?
+            .unwrap_or_default();
+        if rewards.is_zero() {
+            return Ok(vec![]);
+        }
+        REWARDS.remove(&mut staking_storage, (delegator, validator));
+
+        let request = BankQuery::AllBalances {
+            address: delegator.to_string(),
+        };
+        let balance: AllBalanceResponse = from_slice(&bank.query(api, storage, request)?)?;
+        let balance =
+            NativeBalance(balance.amount) + coin(rewards.u128(), self.bonded_denom.as_str());
+        bank.init_balance(storage, delegator, balance.into_vec())?;
+        Ok(vec![Event::new("withdraw_rewards")
+            .add_attribute("validator", validator)
+            .add_attribute("delegator", delegator)
+            .add_attribute(
+                "amount",
+                format!("{}{}", rewards, self.bonded_denom),
+            )])
+    }
+
+    fn assert_bonded(
+        &self,
+        storage: &dyn Storage,
+        validator: &str,
+        amount: &Coin,
+    ) -> AnyResult<()> {
+        if amount.denom != self.bonded_denom {
+            bail!("Cannot bond {}, only {}", amount.denom, self.bonded_denom);
+        }
+        if amount.amount.is_zero() {
+            bail!("Cannot bond nothing");
+        }
+        let staking_storage = prefixed_read(storage, NAMESPACE_STAKING);
+        if !VALIDATORS.has(&staking_storage, validator) {
+            bail!("Unknown validator {}", validator);
+        }
+        Ok(())
+    }
+}
+
+impl Staking for StakeKeeper {
+    fn execute(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        bank: &dyn Bank,
+        sender: Addr,
+        msg: StakingMsg,
+    ) -> AnyResult<AppResponse> {
+        match msg {
+            StakingMsg::Delegate { validator, amount } => {
+                self.assert_bonded(storage, &validator, &amount)?;
+                let mut events = self.withdraw_rewards(api, storage, bank, &sender, &validator)?;
+                let send = BankMsg::Send {
+                    to_address: BONDED_POOL.to_string(),
+                    amount: vec![amount.clone()],
+                };
+                events.extend(bank.execute(storage, sender.clone(), send)?.events);
+
+                let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+                DELEGATIONS.update(
+                    &mut staking_storage,
+                    (&sender, &validator),
+                    |bonded| -> AnyResult<_> { Ok(bonded.unwrap_or_default() + amount.amount) },
+                )?;
+                events.push(
+                    Event::new("delegate")
+                        .add_attribute("validator", validator)
+                        .add_attribute("amount", amount.to_string()),
+                );
+                Ok(AppResponse { events, data: None })
+            }
+            StakingMsg::Undelegate { validator, amount } => {
+                self.assert_bonded(storage, &validator, &amount)?;
+                let mut events = self.withdraw_rewards(api, storage, bank, &sender, &validator)?;
+
+                let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+                let bonded = DELEGATIONS
+                    .may_load(&staking_storage, (&sender, &validator))?
+                    .unwrap_or_default();
+                let bonded = match bonded.checked_sub(amount.amount) {
+                    Ok(bonded) => bonded,
+                    Err(_) => bail!("Cannot undelegate {} of {}", amount.amount, bonded),
+                };
+                if bonded.is_zero() {
+                    DELEGATIONS.remove(&mut staking_storage, (&sender, &validator));
+                } else {
+                    DELEGATIONS.save(&mut staking_storage, (&sender, &validator), &bonded)?;
+                }
+
+                // the unbonding period is skipped, the tokens go back right away
+                let send = BankMsg::Send {
+                    to_address: sender.to_string(),
+                    amount: vec![amount.clone()],
+                };
+                events.extend(
+                    bank.execute(storage, Addr::unchecked(BONDED_POOL), send)?
+                        .events,
+                );
+                events.push(
+                    Event::new("unbond")
+                        .add_attribute("validator", validator)
+                        .add_attribute("amount", amount.to_string()),
+                );
+                Ok(AppResponse { events, data: None })
+            }
+            m => bail!("Unsupported staking message: {:?}", m),
+        }
+    }
+
+    fn execute_distribution(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        bank: &dyn Bank,
+        sender: Addr,
+        msg: DistributionMsg,
+    ) -> AnyResult<AppResponse> {
+        match msg {
+            DistributionMsg::WithdrawDelegatorReward { validator } => {
+                let events = self.withdraw_rewards(api, storage, bank, &sender, &validator)?;
+                Ok(AppResponse { events, data: None })
+            }
+            m => bail!("Unsupported distribution message: {:?}", m),
+        }
+    }
+
+    fn query(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        request: StakingQuery,
+    ) -> AnyResult<Binary> {
+        let staking_storage = prefixed_read(storage, NAMESPACE_STAKING);
+        match request {
+            StakingQuery::BondedDenom {} => Ok(to_binary(&BondedDenomResponse {
+                denom: self.bonded_denom.clone(),
+            })?),
+            StakingQuery::AllDelegations { delegator } => {
+                let delegator = api.addr_validate(&delegator)?;
+                let validators = VALIDATORS
+                    .keys(&staking_storage, None, None, Order::Ascending)
+                    .map(String::from_utf8)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut delegations = vec![];
+                for validator in validators {
+                    if let Some(full) = self.delegation(&staking_storage, &delegator, &validator)? {
+                        delegations.push(Delegation::from(full));
+                    }
+                }
+                Ok(to_binary(&AllDelegationsResponse { delegations })?)
+            }
+            StakingQuery::Delegation {
+                delegator,
+                validator,
+            } => {
+                let delegator = api.addr_validate(&delegator)?;
+                let delegation = self.delegation(&staking_storage, &delegator, &validator)?;
+                Ok(to_binary(&DelegationResponse { delegation })?)
+            }
+            StakingQuery::AllValidators {} => {
+                let validators = VALIDATORS
+                    .range(&staking_storage, None, None, Order::Ascending)
+                    .map(|item| item.map(|(_, validator)| validator))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(to_binary(&AllValidatorsResponse { validators })?)
+            }
+            StakingQuery::Validator { address } => {
+                let validator = VALIDATORS.may_load(&staking_storage, &address)?;
+                Ok(to_binary(&ValidatorResponse { validator })?)
+            }
+            q => bail!("Unsupported staking query: {:?}", q),
+        }
+    }
+
+    fn add_validator(&self, storage: &mut dyn Storage, validator: Validator) -> AnyResult<()> {
+        let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+        VALIDATORS
+            .save(&mut staking_storage, &validator.address.clone(), &validator)
+            .map_err(Into::into)
+    }
+
+    fn add_rewards(
+        &self,
+        storage: &mut dyn Storage,
+        delegator: &Addr,
+        validator: &str,
+        amount: Uint128,
+    ) -> AnyResult<()> {
+        let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+        if !DELEGATIONS.has(&staking_storage, (delegator, validator)) {
+            bail!("{} has no delegation to {}", delegator, validator);
+        }
+        REWARDS.update(
+            &mut staking_storage,
+            (delegator, validator),
+            |rewards| -> AnyResult<_> { Ok(rewards.unwrap_or_default() + amount) },
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::BankKeeper;
+    use cosmwasm_std::testing::{MockApi, MockStorage};
+    use cosmwasm_std::{coins, Decimal};
+
+    fn validator(address: &str) -> Validator {
+        Validator {
+            address: address.to_string(),
+            commission: Decimal::percent(5),
+            max_commission: Decimal::percent(10),
+            max_change_rate: Decimal::percent(1),
+        }
+    }
+
+    fn balance(bank: &BankKeeper, api: &dyn Api, store: &dyn Storage, addr: &str) -> Vec<Coin> {
+        let req = BankQuery::AllBalances {
+            address: addr.to_string(),
+        };
+        let res: AllBalanceResponse = from_slice(&bank.query(api, store, req).unwrap()).unwrap();
+        res.amount
+    }
+
+    fn query_delegation(
+        staking: &StakeKeeper,
+        api: &dyn Api,
+        store: &dyn Storage,
+        delegator: &str,
+        validator: &str,
+    ) -> Option<FullDelegation> {
+        let req = StakingQuery::Delegation {
+            delegator: delegator.to_string(),
+            validator: validator.to_string(),
+        };
+        let res: DelegationResponse = from_slice(&staking.query(api, store, req).unwrap()).unwrap();
+        res.delegation
+    }
+
+    #[test]
+    fn delegate_and_undelegate() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let bank = BankKeeper::new();
+        let staking = StakeKeeper::new("ustake");
+        let owner = Addr::unchecked("owner");
+        bank.init_balance(&mut store, &owner, coins(100, "ustake"))
+            .unwrap();
+        staking.add_validator(&mut store, validator("val")).unwrap();
+
+        // only the bonded denom can be delegated, to known validators
+        let delegate = |validator: &str, amount: Coin| StakingMsg::Delegate {
+            validator: validator.to_string(),
+            amount,
+        };
+        let msg = delegate("val", coin(10, "eth"));
+        staking
+            .execute(&api, &mut store, &bank, owner.clone(), msg)
+            .unwrap_err();
+        let msg = delegate("other", coin(10, "ustake"));
+        staking
+            .execute(&api, &mut store, &bank, owner.clone(), msg)
+            .unwrap_err();
+
+        let msg = delegate("val", coin(60, "ustake"));
+        staking
+            .execute(&api, &mut store, &bank, owner.clone(), msg)
+            .unwrap();
+        assert_eq!(coins(40, "ustake"), balance(&bank, &api, &store, "owner"));
+        assert_eq!(
+            coins(60, "ustake"),
+            balance(&bank, &api, &store, BONDED_POOL)
+        );
+        let delegation = query_delegation(&staking, &api, &store, "owner", "val").unwrap();
+        assert_eq!(coin(60, "ustake"), delegation.amount);
+
+        // rewards are minted on withdrawal and when the delegation changes
+        staking
+            .add_rewards(&mut store, &owner, "val", Uint128::new(5))
+            .unwrap();
+        let delegation = query_delegation(&staking, &api, &store, "owner", "val").unwrap();
+        assert_eq!(coins(5, "ustake"), delegation.accumulated_rewards);
+        let msg = StakingMsg::Undelegate {
+            validator: "val".to_string(),
+            amount: coin(20, "ustake"),
+        };
+        staking
+            .execute(&api, &mut store, &bank, owner.clone(), msg)
+            .unwrap();
+        assert_eq!(coins(65, "ustake"), balance(&bank, &api, &store, "owner"));
+        let delegation = query_delegation(&staking, &api, &store, "owner", "val").unwrap();
+        assert_eq!(coin(40, "ustake"), delegation.amount);
+        assert!(delegation.accumulated_rewards.is_empty());
+
+        staking
+            .add_rewards(&mut store, &owner, "val", Uint128::new(3))
+            .unwrap();
+        let msg = DistributionMsg::WithdrawDelegatorReward {
+            validator: "val".to_string(),
+        };
+        staking
+            .execute_distribution(&api, &mut store, &bank, owner.clone(), msg)
+            .unwrap();
+        assert_eq!(coins(68, "ustake"), balance(&bank, &api, &store, "owner"));
+
+        // more than bonded cannot be undelegated
+        let msg = StakingMsg::Undelegate {
+            validator: "val".to_string(),
+            amount: coin(41, "ustake"),
+        };
+        staking
+            .execute(&api, &mut store, &bank, owner, msg)
+            .unwrap_err();
+    }
+}
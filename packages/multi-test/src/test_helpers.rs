@@ -1,4 +1,3 @@
-#![cfg(test)]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
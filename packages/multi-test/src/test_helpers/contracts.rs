@@ -3,5 +3,6 @@
 pub mod echo;
 pub mod error;
 pub mod hackatom;
+pub mod oracle;
 pub mod payout;
 pub mod reflect;
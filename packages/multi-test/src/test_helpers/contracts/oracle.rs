@@ -0,0 +1,77 @@
+//! Price oracle stand-in. Anyone can set the rate of a currency, contracts read it back
+//! with a `WasmQuery::Smart` of `{"price":{"currency":..}}`.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use cosmwasm_std::{
+    to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+};
+use cw_storage_plus::Map;
+
+use crate::contracts::{Contract, ContractWrapper};
+use crate::test_helpers::EmptyMsg;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    SetPrice { currency: String, rate: Decimal },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Price { currency: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PriceResponse {
+    /// Amount of the native token paid for one unit of the currency
+    pub rate: Decimal,
+}
+
+const PRICES: Map<&str, Decimal> = Map::new("prices");
+
+fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: EmptyMsg,
+) -> Result<Response, StdError> {
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, StdError> {
+    match msg {
+        ExecuteMsg::SetPrice { currency, rate } => {
+            PRICES.save(deps.storage, &currency, &rate)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_price")
+                .add_attribute("currency", currency)
+                .add_attribute("rate", rate.to_string()))
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        QueryMsg::Price { currency } => {
+            let rate = PRICES.load(deps.storage, &currency)?;
+            to_binary(&PriceResponse { rate })
+        }
+    }
+}
+
+pub fn contract<C>() -> Box<dyn Contract<C>>
+where
+    C: Clone + fmt::Debug + PartialEq + JsonSchema + 'static,
+{
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::fmt;
-use std::ops::Deref;
 
 use cosmwasm_std::{
     Addr, Api, Attribute, BankMsg, Binary, BlockInfo, Coin, ContractInfo, ContractResult,
@@ -205,7 +204,7 @@ where
 
     pub fn query_raw(&self, address: Addr, storage: &dyn Storage, key: &[u8]) -> Binary {
         let storage = self.contract_storage_readonly(storage, &address);
-        let data = storage.get(&key).unwrap_or_default();
+        let data = storage.get(key).unwrap_or_default();
         data.into()
     }
 
@@ -675,7 +674,7 @@ where
 
         let deps = Deps {
             storage: storage.as_ref(),
-            api: api.deref(),
+            api,
             querier: QuerierWrapper::new(querier),
         };
         action(handler, deps, env)
@@ -710,7 +709,7 @@ where
 
             let deps = DepsMut {
                 storage: contract_storage.as_mut(),
-                api: api.deref(),
+                api,
                 querier: QuerierWrapper::new(&querier),
             };
             action(handler, deps, env)
@@ -741,7 +740,7 @@ where
             .range(storage, None, None, Order::Ascending)
             .count();
         // we make this longer so it is not rejected by tests
-        Addr::unchecked(format!("Contract #{}", count.to_string()))
+        Addr::unchecked(format!("Contract #{}", count))
     }
 
     fn contract_namespace(&self, contract: &Addr) -> Vec<u8> {
@@ -860,7 +859,7 @@ mod test {
 
     use crate::test_helpers::contracts::{error, payout};
     use crate::transactions::StorageTransaction;
-    use crate::BankKeeper;
+    use crate::{BankKeeper, StakeKeeper};
 
     use super::*;
 
@@ -872,6 +871,7 @@ mod test {
         Router {
             wasm: Box::new(WasmKeeper::new()),
             bank: Box::new(BankKeeper::new()),
+            staking: Box::new(StakeKeeper::default()),
             custom: Box::new(PanickingCustomHandler),
         }
     }
@@ -1026,7 +1026,7 @@ mod test {
         match &res.messages[0].msg {
             CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
                 assert_eq!(to_address.as_str(), "foobar");
-                assert_eq!(amount.as_slice(), &[payout.clone()]);
+                assert_eq!(amount.as_slice(), std::slice::from_ref(&payout));
             }
             m => panic!("Unexpected message {:?}", m),
         }
@@ -1067,7 +1067,7 @@ mod test {
         match &res.messages[0].msg {
             CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
                 assert_eq!(to_address.as_str(), "silly");
-                assert_eq!(amount.as_slice(), &[payout.clone()]);
+                assert_eq!(amount.as_slice(), std::slice::from_ref(payout));
             }
             m => panic!("Unexpected message {:?}", m),
         }
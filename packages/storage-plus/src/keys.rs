@@ -15,7 +15,7 @@ pub trait PrimaryKey<'a>: Clone {
     fn joined_key(&self) -> Vec<u8> {
         let keys = self.key();
         let l = keys.len();
-        namespaces_with_key(&keys[0..l - 1], &keys[l - 1])
+        namespaces_with_key(&keys[0..l - 1], keys[l - 1])
     }
 }
 
@@ -98,7 +98,7 @@ impl<'a> Prefixer<'a> for &'a [u8] {
 impl<'a, T: Prefixer<'a>, U: Prefixer<'a>> Prefixer<'a> for (T, U) {
     fn prefix(&self) -> Vec<&[u8]> {
         let mut res = self.0.prefix();
-        res.extend(self.1.prefix().into_iter());
+        res.extend(self.1.prefix());
         res
     }
 }
@@ -106,8 +106,8 @@ impl<'a, T: Prefixer<'a>, U: Prefixer<'a>> Prefixer<'a> for (T, U) {
 impl<'a, T: Prefixer<'a>, U: Prefixer<'a>, V: Prefixer<'a>> Prefixer<'a> for (T, U, V) {
     fn prefix(&self) -> Vec<&[u8]> {
         let mut res = self.0.prefix();
-        res.extend(self.1.prefix().into_iter());
-        res.extend(self.2.prefix().into_iter());
+        res.extend(self.1.prefix());
+        res.extend(self.2.prefix());
         res
     }
 }
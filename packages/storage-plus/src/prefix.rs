@@ -107,7 +107,7 @@ where
         let de_fn = self.de_fn;
         let pk_name = self.pk_name.clone();
         let mapped = range_with_prefix(store, &self.storage_prefix, min, max, order)
-            .map(move |kv| (de_fn)(store, &*pk_name, kv));
+            .map(move |kv| (de_fn)(store, &pk_name, kv));
         Box::new(mapped)
     }
 